@@ -13,14 +13,9 @@ pub struct Opds {
 
 impl<'a> MayBeFrom<&'a Opds> for Mat<'a> {
     fn maybe_from<S: Into<String>>(name: S, data: &'a Opds) -> matio_rs::Result<Self> {
-        let m: Vec<u8> = data
-            .mask
-            .iter()
-            .map(|&m| if m { 1u8 } else { 0u8 })
-            .collect();
         let mats = vec![
             Mat::maybe_from("values", &data.values)?,
-            Mat::maybe_from("mask", m)?,
+            Mat::maybe_from("mask", &data.mask)?,
         ];
         MayBeFrom::maybe_from(name, mats)
     }
@@ -28,13 +23,9 @@ impl<'a> MayBeFrom<&'a Opds> for Mat<'a> {
 
 impl<'a> MayBeInto<Opds> for Mat<'a> {
     fn maybe_into(self) -> matio_rs::Result<Opds> {
-        let m: Vec<u8> = self.field("values")?.get(0).unwrap().maybe_into()?;
         Ok(Opds {
             values: self.field("values")?.get(0).unwrap().maybe_into()?,
-            mask: m
-                .into_iter()
-                .map(|m| if m == 1 { true } else { false })
-                .collect(),
+            mask: self.field("mask")?.get(0).unwrap().maybe_into()?,
         })
     }
 }
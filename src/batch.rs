@@ -0,0 +1,74 @@
+use crate::{Mat, MatFile, MatFileRead, MayBeInto, Result};
+use rayon::prelude::*;
+use std::path::Path;
+
+impl<'a> MatFile<'a> {
+    /// Loads in parallel the mat files at `paths` and calls `f` on each [MatFileRead]er
+    ///
+    /// The results are returned in the same order as the `paths`,
+    /// a file that fails to open or to be processed does not abort the other files
+    /// ```
+    /// use matio_rs::{MatFile, MatFileRead};
+    /// # let files: Vec<_> = (0..4).map(|_| tempfile::NamedTempFile::new().unwrap()).collect();
+    /// # for (i, file) in files.iter().enumerate() {
+    /// #     MatFile::save(file)?.var("a", i as f64)?;
+    /// # }
+    /// let names: Vec<Vec<String>> = MatFile::par_load(&files, |mat_file: &MatFileRead| {
+    ///     Ok(mat_file.info().into_iter().map(|info| info.name).collect())
+    /// })
+    /// .into_iter()
+    /// .collect::<Result<_, _>>()?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn par_load<P, F, T>(paths: &[P], f: F) -> Vec<Result<T>>
+    where
+        P: AsRef<Path> + Sync,
+        F: Fn(&MatFileRead<'a>) -> Result<T> + Sync,
+        T: Send,
+    {
+        paths
+            .par_iter()
+            .map(|path| {
+                let mat_file = MatFile::load(path)?;
+                let value = f(&mat_file)?;
+                mat_file.close()?;
+                Ok(value)
+            })
+            .collect()
+    }
+    /// Reads in parallel the Matlab variable `name` from each mat file at `paths`, see [par_load](MatFile::par_load)
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let files: Vec<_> = (0..4).map(|_| tempfile::NamedTempFile::new().unwrap()).collect();
+    /// # for (i, file) in files.iter().enumerate() {
+    /// #     MatFile::save(file)?.var("a", vec![i as f64; 3])?;
+    /// # }
+    /// let a: Vec<Vec<f64>> = MatFile::par_var(&files, "a")
+    ///     .into_iter()
+    ///     .collect::<Result<_, _>>()?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn par_var<P, T>(paths: &[P], name: &str) -> Vec<Result<T>>
+    where
+        P: AsRef<Path> + Sync,
+        Mat<'a>: MayBeInto<T>,
+        T: Send,
+    {
+        Self::par_load(paths, |mat_file| mat_file.var(name))
+    }
+    /// Reads in parallel the Matlab variables `names` from each mat file at `paths`, see [par_load](MatFile::par_load)
+    pub fn par_vars<P, S, T>(paths: &[P], names: &[S]) -> Vec<Result<Vec<T>>>
+    where
+        P: AsRef<Path> + Sync,
+        S: AsRef<str> + Sync,
+        Mat<'a>: MayBeInto<T>,
+        T: Send,
+    {
+        Self::par_load(paths, |mat_file| {
+            names
+                .iter()
+                .map(|name| mat_file.var(name.as_ref()))
+                .collect()
+        })
+    }
+}
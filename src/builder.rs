@@ -0,0 +1,159 @@
+use crate::{
+    Compression, Duplicate, MatFile, MatFileWrite, MatioError, Result, Version,
+    matfile::{OnClose, temp_file},
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Maximum length in bytes of a mat file header
+const HEADER_MAX_LEN: usize = 116;
+
+/// [MatFile] saving mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SaveMode {
+    /// Creates the mat file or truncates an existing one
+    #[default]
+    Truncate,
+    /// Creates the mat file and fails with [MatioError::FileExists] if it already exists
+    CreateNew,
+    /// Writes to a temporary file next to the mat file and moves it over the mat file
+    /// only when the [MatFileWrite]r is closed without error with [MatFileWrite::close].
+    ///
+    /// If the [MatFileWrite]r is dropped instead, the temporary file is removed
+    /// and the mat file is left untouched.
+    Atomic,
+}
+
+/// [MatFile] builder
+///
+/// ```
+/// use matio_rs::{Builder, Compression, Version};
+/// # let file = tempfile::NamedTempFile::new().unwrap();
+/// # let data_path = file.path();
+/// Builder::new(data_path)
+///     .version(Version::MAT5)
+///     .header("simulation results")
+///     .compression(Compression::Zlib)
+///     .save()?
+///     .var("a", vec![0f64; 1024])?;
+/// # Ok::<(), matio_rs::MatioError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder {
+    path: PathBuf,
+    version: Version,
+    header: Option<String>,
+    compression: Compression,
+    duplicate: Duplicate,
+    mode: SaveMode,
+}
+impl Builder {
+    /// Creates a new [Builder] for the mat file `path`
+    ///
+    /// The default is a version 5 mat file with matio header and no compression
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            version: Version::default(),
+            header: None,
+            compression: Compression::default(),
+            duplicate: Duplicate::default(),
+            mode: SaveMode::default(),
+        }
+    }
+    /// Sets the mat file format [Version]
+    pub fn version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+    /// Sets the mat file descriptive header text
+    ///
+    /// The header is at most 116 bytes long and is ignored by version 4 mat files
+    pub fn header<S: Into<String>>(mut self, header: S) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+    /// Sets the default [Compression] of the variables
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+    /// Sets the [Duplicate] behavior when writing a variable with the name of a variable already written
+    pub fn on_duplicate(mut self, duplicate: Duplicate) -> Self {
+        self.duplicate = duplicate;
+        self
+    }
+    /// Sets the [SaveMode]
+    ///
+    /// ```
+    /// use matio_rs::{Builder, SaveMode};
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// # let data_path = dir.path().join("data.mat");
+    /// let mat_file = Builder::new(&data_path).mode(SaveMode::Atomic).save()?;
+    /// mat_file.var("a", 1f64)?;
+    /// assert!(!data_path.exists());
+    /// mat_file.close()?;
+    /// assert!(data_path.exists());
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn mode(mut self, mode: SaveMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// Creates the mat file and returns a [MatFileWrite]r
+    pub fn save<'a>(self) -> Result<MatFileWrite<'a>> {
+        if let Some(header) = self.header.as_ref()
+            && header.len() > HEADER_MAX_LEN
+        {
+            return Err(MatioError::Header(header.len()));
+        }
+        let on_close = match self.mode {
+            SaveMode::Truncate => None,
+            SaveMode::CreateNew => {
+                fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&self.path)
+                    .map_err(|e| match e.kind() {
+                        io::ErrorKind::AlreadyExists => {
+                            MatioError::FileExists(self.path.display().to_string())
+                        }
+                        _ => MatioError::Io(e),
+                    })?;
+                None
+            }
+            SaveMode::Atomic => {
+                let name = self
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let dir = self.path.parent().unwrap_or(Path::new(""));
+                let (temp, _) = temp_file(dir, &name, false)?;
+                Some(OnClose::Persist {
+                    temp,
+                    path: self.path.clone(),
+                })
+            }
+        };
+        let path = match &on_close {
+            Some(OnClose::Persist { temp, .. }) => temp,
+            _ => &self.path,
+        };
+        let mut mat_file = match MatFile::create(path, self.header.as_deref(), self.version) {
+            Ok(mat_file) => mat_file,
+            Err(e) => {
+                if self.mode != SaveMode::Truncate {
+                    let _ = fs::remove_file(path);
+                }
+                return Err(e);
+            }
+        };
+        mat_file.compression = self.compression;
+        mat_file.duplicate = self.duplicate;
+        mat_file.on_close = on_close;
+        Ok(MatFileWrite(mat_file))
+    }
+}
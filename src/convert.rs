@@ -2,3 +2,9 @@ mod maybefrom;
 pub use maybefrom::MayBeFrom;
 mod maybeinto;
 pub use maybeinto::MayBeInto;
+mod logical;
+mod char;
+pub(crate) use char::char_rows;
+pub use char::CharEncoding;
+#[cfg(feature = "num-complex")]
+mod complex;
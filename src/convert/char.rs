@@ -0,0 +1,152 @@
+use crate::{Mat, MatioError, Result, mat::slice};
+use std::ffi::CString;
+
+/// Encoding of the Matlab char arrays created with [Mat::char]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CharEncoding {
+    /// UTF-8 char array
+    ///
+    /// The dimensions of a Matlab char array count characters, so only ASCII text
+    /// is written as UTF-8 (one byte per character), any other text is written as UTF-16
+    #[default]
+    Utf8,
+    /// UTF-16 char array, the encoding of the char arrays saved by Matlab
+    Utf16,
+}
+
+impl<'a> Mat<'a> {
+    /// Creates the Matlab char array `name` from `data` with the given [CharEncoding]
+    ///
+    /// ```
+    /// use matio_rs::{CharEncoding, Mat, MatFile};
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// MatFile::save(data_path)?.write(Mat::char("s", "π ≈ 3.14", CharEncoding::Utf16)?)?;
+    /// let s: String = MatFile::load(data_path)?.var("s")?;
+    /// assert_eq!(s, "π ≈ 3.14");
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn char<S: Into<String>>(name: S, data: &str, encoding: CharEncoding) -> Result<Self> {
+        if data.is_empty() {
+            Self::char_matrix(name, &[], encoding)
+        } else {
+            Self::char_matrix(name, &[data], encoding)
+        }
+    }
+    /// Creates the Matlab 2-D char array `name` from the `rows` with the given [CharEncoding]
+    ///
+    /// As with the Matlab `char` function, the rows are padded with trailing spaces to the length of the longest row
+    /// ```
+    /// use matio_rs::{CharEncoding, Mat, MatFile};
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// let mat = Mat::char_matrix("s", &["abc", "de"], CharEncoding::Utf16)?;
+    /// assert_eq!(mat.dims(), vec![2, 3]);
+    /// MatFile::save(data_path)?.write(mat)?;
+    /// let s: Vec<String> = MatFile::load(data_path)?.var("s")?;
+    /// assert_eq!(s, vec!["abc", "de "]);
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn char_matrix<S: Into<String>>(
+        name: S,
+        rows: &[&str],
+        encoding: CharEncoding,
+    ) -> Result<Self> {
+        match encoding {
+            CharEncoding::Utf8 if rows.iter().all(|row| row.is_ascii()) => create(
+                name.into(),
+                rows.iter().map(|row| row.bytes().collect()).collect(),
+                b' ',
+                ffi::matio_types_MAT_T_UINT8,
+            ),
+            CharEncoding::Utf8 | CharEncoding::Utf16 => create(
+                name.into(),
+                rows.iter()
+                    .map(|row| row.encode_utf16().collect())
+                    .collect(),
+                ' ' as u16,
+                ffi::matio_types_MAT_T_UINT16,
+            ),
+        }
+    }
+}
+
+/// Creates a Matlab char array from the code units of each row, padding the rows with `pad`
+fn create<'a, T: Copy>(
+    name: String,
+    rows: Vec<Vec<T>>,
+    pad: T,
+    data_type: ffi::matio_types,
+) -> Result<Mat<'a>> {
+    let c_name = CString::new(name)?;
+    let nrows = rows.len();
+    let ncols = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+    let mut data = vec![pad; nrows * ncols];
+    for (i, row) in rows.iter().enumerate() {
+        for (j, &unit) in row.iter().enumerate() {
+            data[i + j * nrows] = unit;
+        }
+    }
+    let mut dims = [nrows, ncols];
+    let matvar_t = unsafe {
+        ffi::Mat_VarCreate(
+            c_name.as_ptr(),
+            ffi::matio_classes_MAT_C_CHAR,
+            data_type,
+            2,
+            dims.as_mut_ptr(),
+            data.as_ptr() as *mut std::ffi::c_void,
+            0,
+        )
+    };
+    if matvar_t.is_null() {
+        Err(MatioError::MatVarCreate(
+            c_name.to_str().unwrap().to_string(),
+        ))
+    } else {
+        Mat::from_ptr(c_name.to_str().unwrap(), matvar_t)
+    }
+}
+
+/// Splits the column-major `data` of a matrix with `nrows` rows into rows
+fn rows<T: Copy>(data: &[T], nrows: usize) -> impl Iterator<Item = Vec<T>> + '_ {
+    (0..nrows).map(move |i| data.iter().skip(i).step_by(nrows).copied().collect())
+}
+
+/// Decodes the rows of a Matlab char array
+///
+/// UINT8 char arrays are decoded as Latin-1, UTF8 char arrays as UTF-8
+/// and UINT16 and UTF16 char arrays as UTF-16
+///
+/// The characters of a UTF8 char array take a variable number of bytes,
+/// so all the bytes of the array are decoded before being split into rows
+pub(crate) fn char_rows(mat: &Mat<'_>) -> Result<Vec<String>> {
+    let n = mat.len();
+    let nrows = mat.dims().first().copied().unwrap_or_default();
+    let (data, data_type) = unsafe { ((*mat.matvar_t).data, (*mat.matvar_t).data_type) };
+    if n == 0 {
+        return Ok(vec![String::new(); nrows]);
+    }
+    match data_type {
+        ffi::matio_types_MAT_T_UINT16 | ffi::matio_types_MAT_T_UTF16 => {
+            let data = unsafe { slice(data as *const u16, n) };
+            rows(data, nrows)
+                .map(|row| String::from_utf16(&row).map_err(MatioError::from))
+                .collect()
+        }
+        ffi::matio_types_MAT_T_UTF8 => {
+            let nbytes = unsafe { (*mat.matvar_t).nbytes };
+            let data = unsafe { slice(data as *const u8, nbytes) };
+            let chars: Vec<char> = String::from_utf8(data.to_vec())?.chars().collect();
+            Ok(rows(&chars, nrows)
+                .map(|row| row.into_iter().collect())
+                .collect())
+        }
+        _ => {
+            let data = unsafe { slice(data as *const u8, n) };
+            Ok(rows(data, nrows)
+                .map(|row| row.into_iter().map(char::from).collect())
+                .collect())
+        }
+    }
+}
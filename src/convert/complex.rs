@@ -0,0 +1,245 @@
+use crate::{
+    DataType, Mat, MatArray, MatType, MatioError, MayBeFrom, MayBeInto, Result, mat::slice,
+};
+use num_complex::Complex;
+use std::ffi::CString;
+
+/// Creates a complex Matlab variable with split storage of the real and imaginary parts
+fn create<'a, T: Copy>(
+    name: String,
+    class_type: ffi::matio_classes,
+    data_type: ffi::matio_types,
+    dims: &[usize],
+    data: impl Iterator<Item = Complex<T>>,
+) -> Result<Mat<'a>> {
+    let c_name = CString::new(name)?;
+    let (mut re, mut im): (Vec<T>, Vec<T>) = data.map(|c| (c.re, c.im)).unzip();
+    let mut split = ffi::mat_complex_split_t {
+        Re: re.as_mut_ptr() as *mut std::ffi::c_void,
+        Im: im.as_mut_ptr() as *mut std::ffi::c_void,
+    };
+    let matvar_t = unsafe {
+        ffi::Mat_VarCreate(
+            c_name.as_ptr(),
+            class_type,
+            data_type,
+            dims.len() as i32,
+            dims.as_ptr() as *mut _,
+            &mut split as *mut _ as *mut std::ffi::c_void,
+            ffi::matio_flags_MAT_F_COMPLEX as i32,
+        )
+    };
+    if matvar_t.is_null() {
+        Err(MatioError::MatVarCreate(
+            c_name.to_str().unwrap().to_string(),
+        ))
+    } else {
+        Mat::from_ptr(c_name.to_str().unwrap(), matvar_t)
+    }
+}
+
+/// Reads the split storage of a complex Matlab variable
+fn read<T: Copy>(mat: &Mat<'_>) -> Vec<Complex<T>> {
+    let n = mat.len();
+    let data = unsafe { (*mat.matvar_t).data as *const ffi::mat_complex_split_t };
+    if n == 0 || data.is_null() {
+        return Vec::new();
+    }
+    let split = unsafe { &*data };
+    let (re, im) = unsafe {
+        (
+            slice(split.Re as *const T, n),
+            slice(split.Im as *const T, n),
+        )
+    };
+    re.iter()
+        .zip(im)
+        .map(|(&re, &im)| Complex::new(re, im))
+        .collect()
+}
+
+/// Checks that the Matlab variable holds complex numbers of type `T`
+fn check<T>(mat: &Mat<'_>) -> Result<()>
+where
+    Complex<T>: DataType,
+{
+    match mat.mat_type() {
+        Some(mat_type) if <Complex<T> as DataType>::mat_type() == mat_type => Ok(()),
+        _ => Err(MatioError::TypeMismatch(
+            mat.name.clone(),
+            <Complex<T> as DataType>::to_string(),
+            mat.mat_type().map(|t| t.to_string()).unwrap_or_default(),
+        )),
+    }
+}
+
+macro_rules! maybe_complex {
+    ( $( ($rs:ty,$mat_c:expr,$mat_t:expr) ),+ ) => {
+	    $(
+            impl<'a> MayBeFrom<Complex<$rs>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, data: Complex<$rs>) -> Result<Self> {
+                    create(name.into(), $mat_c, $mat_t, &[1, 1], std::iter::once(data))
+                }
+            }
+
+            impl<'a> MayBeFrom<&[Complex<$rs>]> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, data: &[Complex<$rs>]) -> Result<Self> {
+                    let dims = if data.is_empty() { [0, 0] } else { [1, data.len()] };
+                    create(name.into(), $mat_c, $mat_t, &dims, data.iter().cloned())
+                }
+            }
+
+            impl<'a> MayBeFrom<Vec<Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, data: Vec<Complex<$rs>>) -> Result<Self> {
+                    MayBeFrom::<&[Complex<$rs>]>::maybe_from(name, data.as_slice())
+                }
+            }
+
+            impl<'a> MayBeFrom<&Vec<Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, data: &Vec<Complex<$rs>>) -> Result<Self> {
+                    MayBeFrom::<&[Complex<$rs>]>::maybe_from(name, data.as_slice())
+                }
+            }
+
+            impl<'a> MayBeFrom<MatArray<'a, Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, mat_array: MatArray<'a, Complex<$rs>>) -> Result<Self> {
+                    let dims: Vec<usize> = mat_array.dims.iter().map(|&d| d as usize).collect();
+                    create(name.into(), $mat_c, $mat_t, &dims, mat_array.data.iter().cloned())
+                }
+            }
+
+            #[cfg(feature = "nalgebra")]
+            impl<'a> MayBeFrom<nalgebra::DVector<Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, vector: nalgebra::DVector<Complex<$rs>>) -> Result<Self> {
+                    <Mat<'a> as MayBeFrom<&nalgebra::DVector<Complex<$rs>>>>::maybe_from(name, &vector)
+                }
+            }
+            #[cfg(feature = "nalgebra")]
+            impl<'a> MayBeFrom<&nalgebra::DVector<Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, vector: &nalgebra::DVector<Complex<$rs>>) -> Result<Self> {
+                    create(name.into(), $mat_c, $mat_t, &[vector.len(), 1], vector.iter().cloned())
+                }
+            }
+            #[cfg(feature = "nalgebra")]
+            impl<'a> MayBeFrom<nalgebra::DMatrix<Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, matrix: nalgebra::DMatrix<Complex<$rs>>) -> Result<Self> {
+                    <Mat<'a> as MayBeFrom<&nalgebra::DMatrix<Complex<$rs>>>>::maybe_from(name, &matrix)
+                }
+            }
+            #[cfg(feature = "nalgebra")]
+            impl<'a> MayBeFrom<&nalgebra::DMatrix<Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, matrix: &nalgebra::DMatrix<Complex<$rs>>) -> Result<Self> {
+                    create(name.into(), $mat_c, $mat_t, &[matrix.nrows(), matrix.ncols()], matrix.iter().cloned())
+                }
+            }
+
+            #[cfg(feature = "faer")]
+            impl<'a> MayBeFrom<faer::mat::MatRef<'a, Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, matrix: faer::mat::MatRef<'a, Complex<$rs>>) -> Result<Self> {
+                    create(
+                        name.into(),
+                        $mat_c,
+                        $mat_t,
+                        &[matrix.nrows(), matrix.ncols()],
+                        matrix.col_iter().flat_map(|c| c.iter().cloned().collect::<Vec<_>>()),
+                    )
+                }
+            }
+            #[cfg(feature = "faer")]
+            impl<'a> MayBeFrom<&faer::mat::Mat<Complex<$rs>>> for Mat<'a> {
+                fn maybe_from<S: Into<String>>(name: S, matrix: &faer::mat::Mat<Complex<$rs>>) -> Result<Self> {
+                    create(
+                        name.into(),
+                        $mat_c,
+                        $mat_t,
+                        &[matrix.nrows(), matrix.ncols()],
+                        matrix.col_iter().flat_map(|c| c.iter().cloned().collect::<Vec<_>>()),
+                    )
+                }
+            }
+
+            impl<'a> MayBeInto<Complex<$rs>> for &Mat<'a> {
+                fn maybe_into(self) -> Result<Complex<$rs>> {
+                    if self.len() > 1 {
+                        return Err(MatioError::Scalar(self.name.clone(), self.len()));
+                    }
+                    check::<$rs>(self)?;
+                    read(self)
+                        .pop()
+                        .ok_or_else(|| MatioError::Empty(self.name.clone()))
+                }
+            }
+            impl<'a> MayBeInto<Complex<$rs>> for Mat<'a> {
+                fn maybe_into(self) -> Result<Complex<$rs>> {
+                    <&Mat<'a> as MayBeInto<Complex<$rs>>>::maybe_into(&self)
+                }
+            }
+
+            impl<'a> MayBeInto<Vec<Complex<$rs>>> for &Mat<'a> {
+                fn maybe_into(self) -> Result<Vec<Complex<$rs>>> {
+                    if let Some(MatType::CELL) = self.mat_type() {
+                        return self
+                            .cells()?
+                            .iter()
+                            .map(<&Mat<'a> as MayBeInto<Complex<$rs>>>::maybe_into)
+                            .collect();
+                    }
+                    check::<$rs>(self)?;
+                    Ok(read(self))
+                }
+            }
+            impl<'a> MayBeInto<Vec<Complex<$rs>>> for Mat<'a> {
+                fn maybe_into(self) -> Result<Vec<Complex<$rs>>> {
+                    <&Mat<'a> as MayBeInto<Vec<Complex<$rs>>>>::maybe_into(&self)
+                }
+            }
+
+            #[cfg(feature = "nalgebra")]
+            impl<'a> MayBeInto<nalgebra::DMatrix<Complex<$rs>>> for &Mat<'a> {
+                fn maybe_into(self) -> Result<nalgebra::DMatrix<Complex<$rs>>> {
+                    check::<$rs>(self)?;
+                    if self.rank() > 2 {
+                        return Err(MatioError::Rank(self.rank()));
+                    }
+                    let dims = self.dims();
+                    Ok(nalgebra::DMatrix::from_column_slice(
+                        dims[0],
+                        dims[1],
+                        read(self).as_slice(),
+                    ))
+                }
+            }
+            #[cfg(feature = "nalgebra")]
+            impl<'a> MayBeInto<nalgebra::DMatrix<Complex<$rs>>> for Mat<'a> {
+                fn maybe_into(self) -> Result<nalgebra::DMatrix<Complex<$rs>>> {
+                    <&Mat<'a> as MayBeInto<nalgebra::DMatrix<Complex<$rs>>>>::maybe_into(&self)
+                }
+            }
+
+            #[cfg(feature = "faer")]
+            impl<'a> MayBeInto<faer::mat::Mat<Complex<$rs>>> for &Mat<'a> {
+                fn maybe_into(self) -> Result<faer::mat::Mat<Complex<$rs>>> {
+                    check::<$rs>(self)?;
+                    if self.rank() > 2 {
+                        return Err(MatioError::Rank(self.rank()));
+                    }
+                    let dims = self.dims();
+                    let data = read(self);
+                    let mat = faer::MatRef::from_column_major_slice(data.as_slice(), dims[0], dims[1]);
+                    Ok(mat.cloned())
+                }
+            }
+            #[cfg(feature = "faer")]
+            impl<'a> MayBeInto<faer::mat::Mat<Complex<$rs>>> for Mat<'a> {
+                fn maybe_into(self) -> Result<faer::mat::Mat<Complex<$rs>>> {
+                    <&Mat<'a> as MayBeInto<faer::mat::Mat<Complex<$rs>>>>::maybe_into(&self)
+                }
+            }
+		)+
+    };
+}
+
+maybe_complex! {
+    (f64,ffi::matio_classes_MAT_C_DOUBLE,ffi::matio_types_MAT_T_DOUBLE),
+    (f32,ffi::matio_classes_MAT_C_SINGLE,ffi::matio_types_MAT_T_SINGLE)
+}
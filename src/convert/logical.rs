@@ -0,0 +1,108 @@
+use crate::{
+    DataType, Mat, MatArray, MatType, MatioError, MayBeFrom, MayBeInto, Result, mat::slice,
+};
+use std::ffi::CString;
+
+/// Creates a Matlab logical array from booleans stored as uint8
+fn create<'a>(name: String, dims: &[usize], data: &[bool]) -> Result<Mat<'a>> {
+    let c_name = CString::new(name)?;
+    let data: Vec<u8> = data.iter().map(|&b| b as u8).collect();
+    let matvar_t = unsafe {
+        ffi::Mat_VarCreate(
+            c_name.as_ptr(),
+            ffi::matio_classes_MAT_C_UINT8,
+            ffi::matio_types_MAT_T_UINT8,
+            dims.len() as i32,
+            dims.as_ptr() as *mut _,
+            data.as_ptr() as *mut std::ffi::c_void,
+            ffi::matio_flags_MAT_F_LOGICAL as i32,
+        )
+    };
+    if matvar_t.is_null() {
+        Err(MatioError::MatVarCreate(
+            c_name.to_str().unwrap().to_string(),
+        ))
+    } else {
+        Mat::from_ptr(c_name.to_str().unwrap(), matvar_t)
+    }
+}
+
+impl<'a> MayBeFrom<bool> for Mat<'a> {
+    fn maybe_from<S: Into<String>>(name: S, data: bool) -> Result<Self> {
+        create(name.into(), &[1, 1], &[data])
+    }
+}
+
+impl<'a> MayBeFrom<&[bool]> for Mat<'a> {
+    fn maybe_from<S: Into<String>>(name: S, data: &[bool]) -> Result<Self> {
+        let dims = if data.is_empty() {
+            [0, 0]
+        } else {
+            [1, data.len()]
+        };
+        create(name.into(), &dims, data)
+    }
+}
+
+impl<'a> MayBeFrom<Vec<bool>> for Mat<'a> {
+    fn maybe_from<S: Into<String>>(name: S, data: Vec<bool>) -> Result<Self> {
+        MayBeFrom::<&[bool]>::maybe_from(name, data.as_slice())
+    }
+}
+
+impl<'a> MayBeFrom<&Vec<bool>> for Mat<'a> {
+    fn maybe_from<S: Into<String>>(name: S, data: &Vec<bool>) -> Result<Self> {
+        MayBeFrom::<&[bool]>::maybe_from(name, data.as_slice())
+    }
+}
+
+impl<'a> MayBeFrom<MatArray<'a, bool>> for Mat<'a> {
+    fn maybe_from<S: Into<String>>(name: S, mat_array: MatArray<'a, bool>) -> Result<Self> {
+        let dims: Vec<usize> = mat_array.dims.iter().map(|&d| d as usize).collect();
+        create(name.into(), &dims, mat_array.data)
+    }
+}
+
+impl<'a> MayBeInto<Vec<bool>> for &Mat<'a> {
+    fn maybe_into(self) -> Result<Vec<bool>> {
+        match self.mat_type() {
+            Some(mat) if <bool as DataType>::mat_type() == mat => {
+                let data = unsafe { slice((*self.matvar_t).data as *const u8, self.len()) };
+                Ok(data.iter().map(|&b| b != 0).collect())
+            }
+            Some(MatType::CELL) => self
+                .cells()?
+                .iter()
+                .map(<&Mat<'a> as MayBeInto<bool>>::maybe_into)
+                .collect(),
+            _ => Err(MatioError::TypeMismatch(
+                self.name.clone(),
+                <bool as DataType>::to_string(),
+                self.mat_type().map(|t| t.to_string()).unwrap_or_default(),
+            )),
+        }
+    }
+}
+
+impl<'a> MayBeInto<Vec<bool>> for Mat<'a> {
+    fn maybe_into(self) -> Result<Vec<bool>> {
+        <&Mat<'a> as MayBeInto<Vec<bool>>>::maybe_into(&self)
+    }
+}
+
+impl<'a> MayBeInto<bool> for &Mat<'a> {
+    fn maybe_into(self) -> Result<bool> {
+        if self.len() > 1 {
+            return Err(MatioError::Scalar(self.name.clone(), self.len()));
+        }
+        <&Mat<'a> as MayBeInto<Vec<bool>>>::maybe_into(self)?
+            .pop()
+            .ok_or_else(|| MatioError::Empty(self.name.clone()))
+    }
+}
+
+impl<'a> MayBeInto<bool> for Mat<'a> {
+    fn maybe_into(self) -> Result<bool> {
+        <&Mat<'a> as MayBeInto<bool>>::maybe_into(&self)
+    }
+}
@@ -1,6 +1,6 @@
 use std::{ffi::CString, marker::PhantomData, ptr, vec};
 
-use crate::{Mat, MatArray, MatioError, Result};
+use crate::{CharEncoding, Mat, MatArray, MatioError, Result};
 
 /// Convert a Rust data type into a [Mat] variable
 pub trait MayBeFrom<T> {
@@ -41,7 +41,7 @@ macro_rules! maybe_from {
             impl<'a> MayBeFrom<&[$rs]> for Mat<'a> {
                 fn maybe_from<S: Into<String>>(name: S, data: &[$rs]) -> Result<Self> {
                     let c_name = CString::new(name.into())?;
-                    let mut dims = [1, data.len()];
+                    let mut dims = if data.is_empty() { [0, 0] } else { [1, data.len()] };
                     let matvar_t = unsafe {
                         ffi::Mat_VarCreate(
                             c_name.as_ptr(),
@@ -322,7 +322,6 @@ impl<'a> MayBeFrom<VecArray<'a>> for Mat<'a> {
             matvar_t,
             fields: Some(fields.into_iter().flatten().collect()),
             marker: PhantomData,
-            as_ref: false,
         })
     }
 }
@@ -340,26 +339,7 @@ impl<'a> MayBeFrom<MatIterator<'a>> for Mat<'a> {
 
 impl<'a> MayBeFrom<&str> for Mat<'a> {
     fn maybe_from<S: Into<String>>(name: S, data: &str) -> Result<Self> {
-        let c_name = CString::new(name.into())?;
-        let mut dims = [1, data.len()];
-        let matvar_t = unsafe {
-            ffi::Mat_VarCreate(
-                c_name.as_ptr(),
-                ffi::matio_classes_MAT_C_CHAR,
-                ffi::matio_types_MAT_T_UINT8,
-                2,
-                dims.as_mut_ptr(),
-                data.as_ptr() as *mut std::ffi::c_void,
-                0,
-            )
-        };
-        if matvar_t.is_null() {
-            Err(MatioError::MatVarCreate(
-                c_name.to_str().unwrap().to_string(),
-            ))
-        } else {
-            Mat::from_ptr(c_name.to_str().unwrap(), matvar_t)
-        }
+        Mat::char(name, data, CharEncoding::default())
     }
 }
 
@@ -377,41 +357,16 @@ impl<'a> MayBeFrom<&String> for Mat<'a> {
 
 impl<'a> MayBeFrom<&[&str]> for Mat<'a> {
     fn maybe_from<S: Into<String>>(name: S, data: &[&str]) -> Result<Self> {
-        let c_name = CString::new(name.into())?;
-        let mut dims = [1, data.len()];
-        let matcell_t = unsafe {
-            ffi::Mat_VarCreate(
-                c_name.as_ptr(),
-                ffi::matio_classes_MAT_C_CELL,
-                ffi::matio_types_MAT_T_CELL,
-                2,
-                dims.as_mut_ptr(),
-                std::ptr::null_mut(),
-                0,
-            )
-        };
-        for (i, s) in data.into_iter().enumerate() {
-            let mut dims = [1, s.len()];
-            unsafe {
-                let matvar_t = ffi::Mat_VarCreate(
-                    std::ptr::null_mut(),
-                    ffi::matio_classes_MAT_C_CHAR,
-                    ffi::matio_types_MAT_T_UINT8,
-                    2,
-                    dims.as_mut_ptr(),
-                    s.as_ptr() as *mut std::ffi::c_void,
-                    0,
-                );
-                ffi::Mat_VarSetCell(matcell_t, i as i32, matvar_t);
-            };
-        }
-        if matcell_t.is_null() {
-            Err(MatioError::MatVarCreate(
-                c_name.to_str().unwrap().to_string(),
-            ))
+        let cells = data
+            .iter()
+            .map(|s| Mat::char("", s, CharEncoding::default()))
+            .collect::<Result<Vec<_>>>()?;
+        let dims = if data.is_empty() {
+            vec![0, 0]
         } else {
-            Mat::from_ptr(c_name.to_str().unwrap(), matcell_t)
-        }
+            vec![1, data.len()]
+        };
+        Mat::cell(name, cells, dims)
     }
 }
 impl<'a> MayBeFrom<Vec<&str>> for Mat<'a> {
@@ -448,3 +403,38 @@ impl<'a> MayBeFrom<&Vec<&str>> for Mat<'a> {
         <Mat<'a> as MayBeFrom<&[&str]>>::maybe_from(name, data)
     }
 }
+
+/// [None] is converted into an empty (0x0) Matlab double array
+impl<'a, T> MayBeFrom<Option<T>> for Mat<'a>
+where
+    Mat<'a>: MayBeFrom<T>,
+{
+    fn maybe_from<S: Into<String>>(name: S, data: Option<T>) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        if let Some(data) = data {
+            return <Mat<'a> as MayBeFrom<T>>::maybe_from(name, data);
+        }
+        let c_name = CString::new(name.into())?;
+        let mut dims = [0, 0];
+        let matvar_t = unsafe {
+            ffi::Mat_VarCreate(
+                c_name.as_ptr(),
+                ffi::matio_classes_MAT_C_DOUBLE,
+                ffi::matio_types_MAT_T_DOUBLE,
+                2,
+                dims.as_mut_ptr(),
+                ptr::null_mut(),
+                0,
+            )
+        };
+        if matvar_t.is_null() {
+            Err(MatioError::MatVarCreate(
+                c_name.to_str().unwrap().to_string(),
+            ))
+        } else {
+            Mat::from_ptr(c_name.to_str().unwrap(), matvar_t)
+        }
+    }
+}
@@ -1,5 +1,4 @@
-use crate::{DataType, Mat, MatioError, Result};
-use std::ptr;
+use crate::{DataType, Mat, MatType, MatioError, Result, convert::char_rows, mat::slice};
 
 /// Convert a [Mat] variable into a Rust data type
 pub trait MayBeInto<T> {
@@ -16,7 +15,10 @@ macro_rules! maybe_into {
                     }
                     match self.mat_type() {
                         Some(mat) if <$rs as DataType>::mat_type() == mat => {
-                            Ok(unsafe { ((*self.matvar_t).data as *mut $rs).read() })
+                            unsafe { slice((*self.matvar_t).data as *const $rs, self.len()) }
+                                .first()
+                                .copied()
+                                .ok_or_else(|| MatioError::Empty(self.name.clone()))
                         }
                         _ => Err(MatioError::TypeMismatch(
                             self.name.clone(),
@@ -36,15 +38,15 @@ macro_rules! maybe_into {
         impl<'a> MayBeInto<Vec<$rs>> for &Mat<'a> {
             fn maybe_into(self) -> Result<Vec<$rs>> {
                 match self.mat_type() {
-                    Some(mat) if <$rs as DataType>::mat_type() == mat => {
-                        let n = self.len();
-                        let mut value: Vec<$rs> = Vec::with_capacity(n);
-                        unsafe {
-                            ptr::copy((*self.matvar_t).data as *mut $rs, value.as_mut_ptr(), n);
-                            value.set_len(n);
-                        }
-                        Ok(value)
+                    Some(mat) if <$rs as DataType>::mat_type() == mat => Ok(unsafe {
+                        slice((*self.matvar_t).data as *const $rs, self.len())
                     }
+                    .to_vec()),
+                    Some(MatType::CELL) => self
+                        .cells()?
+                        .iter()
+                        .map(<&Mat<'a> as MayBeInto<$rs>>::maybe_into)
+                        .collect(),
                     _ => Err(MatioError::TypeMismatch(
                         self.name.clone(),
                         <$rs as DataType>::to_string(),
@@ -61,6 +63,21 @@ macro_rules! maybe_into {
             }
         }
 
+        impl<'a> MayBeInto<Vec<Vec<$rs>>> for &Mat<'a> {
+            fn maybe_into(self) -> Result<Vec<Vec<$rs>>> {
+                self.cells()?
+                    .iter()
+                    .map(<&Mat<'a> as MayBeInto<Vec<$rs>>>::maybe_into)
+                    .collect()
+            }
+        }
+
+        impl<'a> MayBeInto<Vec<Vec<$rs>>> for Mat<'a> {
+            fn maybe_into(self) -> Result<Vec<Vec<$rs>>> {
+                <&Mat<'a> as MayBeInto<Vec<Vec<$rs>>>>::maybe_into(&self)
+            }
+        }
+
         #[cfg(feature = "nalgebra")]
         impl<'a>
             MayBeInto<nalgebra::DMatrix<$rs>> for &Mat<'a>
@@ -155,17 +172,15 @@ impl<'a> MayBeInto<Mat<'a>> for Mat<'a> {
     }
 }
 
-impl<'a> MayBeInto<String> for Mat<'a> {
+impl<'a> MayBeInto<String> for &Mat<'a> {
     fn maybe_into(self) -> Result<String> {
         match self.mat_type() {
             Some(mat) if <String as DataType>::mat_type() == mat => {
-                let n = self.len();
-                let mut value: Vec<u8> = Vec::with_capacity(n);
-                unsafe {
-                    ptr::copy((*self.matvar_t).data as *mut u8, value.as_mut_ptr(), n);
-                    value.set_len(n);
+                let mut rows = char_rows(self)?;
+                if rows.len() > 1 {
+                    return Err(MatioError::CharRows(self.name.clone(), rows.len()));
                 }
-                Ok(String::from_utf8(value)?)
+                Ok(rows.pop().unwrap_or_default())
             }
             _ => Err(MatioError::TypeMismatch(
                 self.name.clone(),
@@ -176,20 +191,21 @@ impl<'a> MayBeInto<String> for Mat<'a> {
     }
 }
 
-impl<'a> MayBeInto<Vec<String>> for Mat<'a> {
+impl<'a> MayBeInto<String> for Mat<'a> {
+    fn maybe_into(self) -> Result<String> {
+        <&Mat<'a> as MayBeInto<String>>::maybe_into(&self)
+    }
+}
+
+impl<'a> MayBeInto<Vec<String>> for &Mat<'a> {
     fn maybe_into(self) -> Result<Vec<String>> {
         match self.mat_type() {
-            Some(mat) if <Vec<String> as DataType>::mat_type() == mat => {
-                let n = self.len();
-                let mut value: Vec<String> = Vec::with_capacity(n);
-                for i in 0..n {
-                    let matvar_t = unsafe { ffi::Mat_VarGetCell(self.matvar_t, i as i32) };
-                    let mat = Mat::as_ptr(String::new(), matvar_t)?;
-                    let rs = <Mat<'a> as MayBeInto<String>>::maybe_into(mat)?;
-                    value.push(rs);
-                }
-                Ok(value)
-            }
+            Some(mat) if <Vec<String> as DataType>::mat_type() == mat => self
+                .cells()?
+                .iter()
+                .map(<&Mat<'a> as MayBeInto<String>>::maybe_into)
+                .collect(),
+            Some(MatType::CHAR) => char_rows(self),
             _ => Err(MatioError::TypeMismatch(
                 self.name.clone(),
                 <Vec<String> as DataType>::to_string(),
@@ -198,3 +214,36 @@ impl<'a> MayBeInto<Vec<String>> for Mat<'a> {
         }
     }
 }
+
+impl<'a> MayBeInto<Vec<String>> for Mat<'a> {
+    fn maybe_into(self) -> Result<Vec<String>> {
+        <&Mat<'a> as MayBeInto<Vec<String>>>::maybe_into(&self)
+    }
+}
+
+/// An empty Matlab array is converted into [None]
+impl<'a, 'b, T> MayBeInto<Option<T>> for &'b Mat<'a>
+where
+    &'b Mat<'a>: MayBeInto<T>,
+{
+    fn maybe_into(self) -> Result<Option<T>> {
+        if self.is_empty() {
+            Ok(None)
+        } else {
+            self.maybe_into().map(Some)
+        }
+    }
+}
+
+impl<'a, T> MayBeInto<Option<T>> for Mat<'a>
+where
+    Mat<'a>: MayBeInto<T>,
+{
+    fn maybe_into(self) -> Result<Option<T>> {
+        if self.is_empty() {
+            Ok(None)
+        } else {
+            self.maybe_into().map(Some)
+        }
+    }
+}
@@ -6,10 +6,21 @@ pub trait DataType {
     fn to_string() -> String;
 }
 
+/// Rust numeric types mapping to Matlab numeric classes
+pub trait Numeric: DataType + Copy + Default {
+    /// Returns the Matlab class of the Rust numeric type
+    fn mat_class() -> MatClass;
+}
+
 macro_rules! map {
     ( $( ($rs:ty,$mat:expr) ),+ ) => {
 	    $(
         paste! {
+            impl Numeric for $rs {
+            fn mat_class() -> MatClass {
+                MatClass::$mat
+            }
+            }
             impl DataType for $rs {
             fn mat_type() -> MatType {
                 MatType::$mat
@@ -59,6 +70,36 @@ impl DataType for Vec<String> {
     }
 }
 
+impl DataType for bool {
+    fn mat_type() -> MatType {
+        MatType::LOGICAL
+    }
+
+    fn to_string() -> String {
+        "bool".into()
+    }
+}
+#[cfg(feature = "num-complex")]
+impl DataType for num_complex::Complex<f64> {
+    fn mat_type() -> MatType {
+        MatType::COMPLEX_DOUBLE
+    }
+
+    fn to_string() -> String {
+        "Complex<f64>".into()
+    }
+}
+#[cfg(feature = "num-complex")]
+impl DataType for num_complex::Complex<f32> {
+    fn mat_type() -> MatType {
+        MatType::COMPLEX_SINGLE
+    }
+
+    fn to_string() -> String {
+        "Complex<f32>".into()
+    }
+}
+
 map! {
 (f64, DOUBLE),
 (f32, SINGLE),
@@ -72,6 +113,7 @@ map! {
 (u64, UINT64)
 }
 
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Eq)]
 pub enum MatType {
     DOUBLE,
@@ -87,17 +129,30 @@ pub enum MatType {
     STRUCT,
     CHAR,
     CELL,
+    COMPLEX_DOUBLE,
+    COMPLEX_SINGLE,
+    LOGICAL,
+    SPARSE,
+    COMPLEX_SPARSE,
+    LOGICAL_SPARSE,
 }
 
 macro_rules! impl_mat_type {
-    ( $( ($mat_c:expr,$mat_t:expr) ),+ ) => {
+    ( $( ($mat:ident,$mat_c:ident,$($mat_t:ident)|+,$complex:literal,$logical:literal) ),+ ) => {
         paste! {
         impl MatType {
             pub fn from_ptr(ptr: *const ffi::matvar_t) -> Option<Self >{
-                let mat_ct = unsafe { ((*ptr).class_type, (*ptr).data_type) };
+                let mat_ct = unsafe {
+                    (
+                        (*ptr).class_type,
+                        (*ptr).data_type,
+                        (*ptr).isComplex != 0,
+                        (*ptr).isLogical != 0,
+                    )
+                };
                 match mat_ct {
                     $(
-                    (ffi::[<matio_classes_MAT_C_ $mat_c>], ffi::[<matio_types_MAT_T_ $mat_t>]) => Some(MatType::$mat_c),
+                    (ffi::[<matio_classes_MAT_C_ $mat_c>], $(ffi::[<matio_types_MAT_T_ $mat_t>])|+, $complex, $logical) => Some(MatType::$mat),
                     )+
                     _ => None
                 }
@@ -105,7 +160,7 @@ macro_rules! impl_mat_type {
             pub fn to_string(&self) -> String {
                 match self {
                     $(
-                        MatType::$mat_c => stringify!($mat_c).to_string(),
+                        MatType::$mat => stringify!($mat).to_string(),
                     )+
                 }
             }
@@ -115,17 +170,101 @@ macro_rules! impl_mat_type {
 }
 
 impl_mat_type! {
- (DOUBLE,DOUBLE),
- (SINGLE,SINGLE),
- (INT8,INT8),
- (INT16,INT16),
- (INT32,INT32),
- (INT64,INT64),
- (UINT8,UINT8),
- (UINT16,UINT16),
- (UINT32,UINT32),
- (UINT64,UINT64),
- (STRUCT,STRUCT),
- (CHAR,UTF8),
- (CELL,CELL)
+ (DOUBLE,DOUBLE,DOUBLE,false,false),
+ (SINGLE,SINGLE,SINGLE,false,false),
+ (INT8,INT8,INT8,false,false),
+ (INT16,INT16,INT16,false,false),
+ (INT32,INT32,INT32,false,false),
+ (INT64,INT64,INT64,false,false),
+ (UINT8,UINT8,UINT8,false,false),
+ (UINT16,UINT16,UINT16,false,false),
+ (UINT32,UINT32,UINT32,false,false),
+ (UINT64,UINT64,UINT64,false,false),
+ (STRUCT,STRUCT,STRUCT,false,false),
+ (CHAR,CHAR,UTF8|UINT8|UINT16|UTF16,false,false),
+ (CELL,CELL,CELL,false,false),
+ (COMPLEX_DOUBLE,DOUBLE,DOUBLE,true,false),
+ (COMPLEX_SINGLE,SINGLE,SINGLE,true,false),
+ (LOGICAL,UINT8,UINT8,false,true),
+ (SPARSE,SPARSE,DOUBLE,false,false),
+ (COMPLEX_SPARSE,SPARSE,DOUBLE,true,false),
+ (LOGICAL_SPARSE,SPARSE,DOUBLE|UINT8,false,true)
+}
+
+macro_rules! impl_ffi_enum {
+    ( $enum:ident, $ffi:ident, $prefix:ident, $default:ident, [ $( $var:ident ),+ ] ) => {
+        paste! {
+        impl $enum {
+            pub(crate) fn from_ffi(value: ffi::$ffi) -> Self {
+                match value {
+                    $(
+                    ffi::[<$prefix $var>] => $enum::$var,
+                    )+
+                    _ => $enum::$default,
+                }
+            }
+        }
+        }
+    };
+}
+
+/// Matlab class of a variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatClass {
+    EMPTY,
+    CELL,
+    STRUCT,
+    OBJECT,
+    CHAR,
+    SPARSE,
+    DOUBLE,
+    SINGLE,
+    INT8,
+    UINT8,
+    INT16,
+    UINT16,
+    INT32,
+    UINT32,
+    INT64,
+    UINT64,
+    FUNCTION,
+    OPAQUE,
+}
+
+impl_ffi_enum! {
+    MatClass, matio_classes, matio_classes_MAT_C_, EMPTY,
+    [EMPTY, CELL, STRUCT, OBJECT, CHAR, SPARSE, DOUBLE, SINGLE, INT8, UINT8,
+     INT16, UINT16, INT32, UINT32, INT64, UINT64, FUNCTION, OPAQUE]
+}
+
+/// Data type of the elements of a Matlab variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatDataType {
+    UNKNOWN,
+    INT8,
+    UINT8,
+    INT16,
+    UINT16,
+    INT32,
+    UINT32,
+    SINGLE,
+    DOUBLE,
+    INT64,
+    UINT64,
+    MATRIX,
+    COMPRESSED,
+    UTF8,
+    UTF16,
+    UTF32,
+    STRING,
+    CELL,
+    STRUCT,
+    ARRAY,
+    FUNCTION,
+}
+
+impl_ffi_enum! {
+    MatDataType, matio_types, matio_types_MAT_T_, UNKNOWN,
+    [UNKNOWN, INT8, UINT8, INT16, UINT16, INT32, UINT32, SINGLE, DOUBLE, INT64,
+     UINT64, MATRIX, COMPRESSED, UTF8, UTF16, UTF32, STRING, CELL, STRUCT, ARRAY, FUNCTION]
 }
@@ -53,7 +53,7 @@ let mat_v = Mat::maybe_from("fb", &b)?;
 let data = vec![mat_a, mat_v];
 let mat_struct = Mat::maybe_from("s", data)?;
 let mat_file = MatFile::save(data_path)?;
-mat_file.write(mat_struct);
+mat_file.write(mat_struct)?;
 # Ok::<(), matio_rs::MatioError>(())
 ```
 and then loading the structure fields back into Rust variables
@@ -68,7 +68,7 @@ use matio_rs::{MatFile, Mat, MayBeInto};
 # let data = vec![mat_a, mat_v];
 # let mat_struct = Mat::maybe_from("s", data)?;
 # let mat_file = MatFile::save(data_path)?;
-# mat_file.write(mat_struct);
+# mat_file.write(mat_struct)?;
 let mat_file = MatFile::load(&data_path)?;
 let mat: Mat = mat_file.var("s")?;
 let a: f64 = mat
@@ -124,6 +124,21 @@ MatFile::save(&file)?
 # Ok::<(), matio_rs::MatioError>(())
 ```
 
+[None] is saved as an empty Matlab array and any empty Matlab array is loaded as [None]
+```
+use matio_rs::MatFile;
+# use tempfile::NamedTempFile;
+# let file = NamedTempFile::new().unwrap();
+MatFile::save(&file)?
+        .var("a", None::<f64>)?
+        .var("b", Vec::<f64>::new())?;
+let mat_file = MatFile::load(&file)?;
+let a: Option<f64> = mat_file.var("a")?;
+let b: Option<Vec<f64>> = mat_file.var("b")?;
+assert_eq!((a, b), (None, None));
+# Ok::<(), matio_rs::MatioError>(())
+```
+
 [nalgebra](https://docs.rs/nalgebra/latest/nalgebra/) vectors and matrices can be read from and
  written to Mat files providing the `nalgebra` feature
 ```
@@ -142,54 +157,129 @@ let na_m = nalgebra::DMatrix::from_iterator(3, 2, 0..6);
 MatFile::save(&file).unwrap().var("na_m", &na_m).unwrap();
 let m: nalgebra::DMatrix<i32> = MatFile::load(file).unwrap().var("na_m").unwrap();
 ```
+
+Complex numbers are read from and written to Mat files providing the `num-complex` feature
+```
+use matio_rs::MatFile;
+use num_complex::Complex;
+# use tempfile::NamedTempFile;
+# let file = NamedTempFile::new().unwrap();
+let z: Vec<_> = (0..5).map(|i| Complex::new(i as f64, 1.)).collect();
+MatFile::save(&file)?.var("z", &z)?;
+let zz: Vec<Complex<f64>> = MatFile::load(file)?.var("z")?;
+# Ok::<(), matio_rs::MatioError>(())
+```
+
+Many Mat files can be read in parallel providing the `rayon` feature
+```
+use matio_rs::MatFile;
+# use tempfile::NamedTempFile;
+# let files: Vec<_> = (0..4).map(|_| NamedTempFile::new().unwrap()).collect();
+# for file in &files {
+#     MatFile::save(file)?.var("a", vec![1f64; 3])?;
+# }
+for a in MatFile::par_var::<_, Vec<f64>>(&files, "a") {
+    println!("{:?}", a);
+}
+# Ok::<(), matio_rs::MatioError>(())
+```
 */
 
-use std::{io, string::FromUtf8Error};
+use std::{
+    io,
+    string::{FromUtf8Error, FromUtf16Error},
+};
 use thiserror::Error;
 
-// mod builder;
-// pub use builder::Builder;
+mod builder;
+pub use builder::{Builder, SaveMode};
 mod matfile;
-pub use matfile::{MatFile, MatFileRead, MatFileWrite};
+pub use matfile::{
+    Compression, Duplicate, MatFile, MatFileRead, MatFileReadWrite, MatFileWrite, Version,
+};
 mod datatype;
 pub(crate) use datatype::{DataType, MatType};
+pub use datatype::{MatClass, MatDataType, Numeric};
 mod mat;
-pub use mat::Mat;
+pub use mat::{Mat, MatIter};
 mod convert;
-pub use convert::{MayBeFrom, MayBeInto};
+pub use convert::{CharEncoding, MayBeFrom, MayBeInto};
 pub use derive::MatIO;
 mod mat_array;
 pub use mat_array::MatArray;
+mod var_info;
+pub use var_info::VarInfo;
+mod workspace;
+pub use workspace::Workspace;
+mod subset;
+pub use subset::Chunks;
+mod sparse;
+pub use sparse::{SparseElement, SparseMat};
+#[cfg(feature = "rayon")]
+mod batch;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum MatioError {
-    #[error("mat file does not exists")]
-    NoFile(#[from] io::Error),
+    #[error("mat file {0} does not exist: {1}")]
+    NoFile(String, #[source] io::Error),
+    #[error("mat file I/O error: {0}")]
+    Io(#[from] io::Error),
     #[error("opening mat file {0} failed")]
     MatOpen(String),
+    #[error("closing mat file {0} failed")]
+    MatClose(String),
+    #[error("mat file {0} already exists")]
+    FileExists(String),
+    #[error("failed to move temporary mat file to {0}")]
+    Persist(String, #[source] io::Error),
     #[error("mat file name can't be processed")]
     MatName(#[from] std::ffi::NulError),
+    #[error("mat file path {0} is not valid UTF-8")]
+    PathEncoding(String),
     #[error("reading mat var {0} failed")]
     MatVarRead(String),
     #[error("creating mat var {0} failed")]
     MatVarCreate(String),
+    #[error("writing mat var {0} failed")]
+    MatVarWrite(String),
+    #[error("deleting mat var {0} failed")]
+    MatVarDelete(String),
+    #[error("mat var {0} not found")]
+    MatVarNotFound(String),
+    #[error("mat var {0} already exists")]
+    MatVarExists(String),
     #[error("Rust ({0}) and Matlab ({1}) types do not match")]
     MatType(String, String),
     #[error("structure fields missing")]
     NoFields,
     #[error("structure fields have different sizes {0:?}")]
     FieldSize(Vec<usize>),
+    #[error("{0} cells do not match the cell array dimensions {1:?}")]
+    CellSize(usize, Vec<usize>),
     #[error("Matlab var. {0}: expected Matlab type {1} found {2}")]
     TypeMismatch(String, String, String),
-    #[error("Matlab var. {0}: cannot convert a Matlab array of length {0} into a Rust scalar")]
+    #[error("Matlab var. {0}: invalid subset, {1}")]
+    Subset(String, String),
+    #[error("Matlab var. {0}: cannot convert a Matlab array of length {1} into a Rust scalar")]
     Scalar(String, usize),
+    #[error("Matlab var. {0}: cannot convert an empty Matlab array into a Rust scalar")]
+    Empty(String),
+    #[error("Matlab var. {0}: cannot convert a {1} rows char array into a String, read it as a Vec<String>")]
+    CharRows(String, usize),
     #[error("Field name cannot be converted to &str")]
     FieldName(#[from] std::str::Utf8Error),
     #[error("Field {0} not found")]
     FieldNotFound(String),
+    #[error("mat file header is {0} bytes long, the maximum is 116 bytes")]
+    Header(usize),
+    #[error("invalid sparse matrix: {0}")]
+    Sparse(String),
     #[error("expected rank 2, found {0}")]
     Rank(usize),
     #[error("failed to convert Matlab char array to String")]
     CharConversion(#[from] FromUtf8Error),
+    #[error("failed to convert Matlab UTF-16 char array to String")]
+    Utf16Conversion(#[from] FromUtf16Error),
 }
 pub type Result<T> = std::result::Result<T, MatioError>;
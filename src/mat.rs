@@ -1,16 +1,29 @@
 use crate::{
-    MatArray, MatFile, MatFileRead, MatFileWrite, MatType, MatioError, MayBeFrom, MayBeInto, Result,
+    Compression, Duplicate, MatArray, MatFile, MatFileRead, MatFileReadWrite, MatFileWrite,
+    MatType, MatioError, MayBeFrom, MayBeInto, Result,
+};
+use std::{
+    ffi::{CStr, CString},
+    marker::PhantomData,
+    ptr,
+    slice::from_raw_parts,
 };
-use std::{ffi::CStr, marker::PhantomData, ptr, slice::from_raw_parts};
 
 /// Matlab variable
+///
+/// A [Mat] variable can be moved to another thread but it cannot be shared between threads:
+/// matio takes a mutable pointer to the variable when it is written to a [MatFile]
+/// and it does not guarantee that the variable is left untouched.
 pub struct Mat<'a> {
     pub(crate) name: String,
     pub(crate) matvar_t: *mut ffi::matvar_t,
     pub(crate) fields: Option<Vec<Mat<'a>>>,
     pub(crate) marker: PhantomData<&'a ffi::matvar_t>,
-    pub(crate) as_ref: bool,
 }
+// SAFETY: a [Mat] owns the memory of `matvar_t`, including the data and the fields,
+// that is detached from the [MatFile] it has been read from, and matio does not keep
+// any global reference to a `matvar_t`
+unsafe impl<'a> Send for Mat<'a> {}
 impl<'a> Drop for Mat<'a> {
     fn drop(&mut self) {
         if let Some(mut fields) = self.fields.take() {
@@ -18,10 +31,8 @@ impl<'a> Drop for Mat<'a> {
                 mat.matvar_t = ptr::null_mut();
             })
         }
-        if !self.as_ref {
-            unsafe {
-                ffi::Mat_VarFree(self.matvar_t);
-            }
+        unsafe {
+            ffi::Mat_VarFree(self.matvar_t);
         }
     }
 }
@@ -31,21 +42,39 @@ impl<'a> MatFile<'a> {
         let c_name = std::ffi::CString::new(name.into())?;
         let matvar_t = unsafe { ffi::Mat_VarRead(self.mat_t, c_name.as_ptr()) };
         if matvar_t.is_null() {
-            Err(MatioError::MatVarRead(c_name.to_str().unwrap().to_string()))
+            let var_name = c_name.to_string_lossy().into_owned();
+            if self.has_var(&c_name) {
+                Err(MatioError::MatVarRead(var_name))
+            } else {
+                Err(MatioError::MatVarNotFound(var_name))
+            }
         } else {
             Mat::from_ptr(c_name.to_str()?, matvar_t)
         }
     }
     /// Write to a [MatFile] the Matlab [Mat] variable `name`
-    pub fn write(&self, var: Mat<'a>) -> &Self {
-        unsafe {
-            ffi::Mat_VarWrite(
-                self.mat_t,
-                var.matvar_t,
-                ffi::matio_compression_MAT_COMPRESSION_NONE,
-            );
+    pub fn write(&self, var: Mat<'a>) -> Result<&Self> {
+        self.write_var(&var, self.compression)?;
+        Ok(self)
+    }
+    pub(crate) fn write_var(&self, var: &Mat<'a>, compression: Compression) -> Result<()> {
+        let c_name = CString::new(var.name.as_str())?;
+        if self.in_dir(&c_name) {
+            match self.duplicate {
+                Duplicate::Error => return Err(MatioError::MatVarExists(var.name.clone())),
+                Duplicate::Replace => {
+                    if unsafe { ffi::Mat_VarDelete(self.mat_t, c_name.as_ptr()) } != 0 {
+                        return Err(MatioError::MatVarDelete(var.name.clone()));
+                    }
+                }
+            }
+        }
+        let status = unsafe { ffi::Mat_VarWrite(self.mat_t, var.matvar_t, compression.to_ffi()) };
+        if status != 0 {
+            Err(MatioError::MatVarWrite(var.name.clone()))
+        } else {
+            Ok(())
         }
-        self
     }
 }
 impl<'a> MatFileRead<'a> {
@@ -79,6 +108,55 @@ impl<'a> MatFileRead<'a> {
         self.read(name).and_then(|mat| mat.maybe_into())
     }
 }
+/// Iterator over the Matlab [Mat] variables of a [MatFileRead]er
+///
+/// The variables are read in file order
+pub struct MatIter<'a, 'b> {
+    mat_file: &'b MatFile<'a>,
+}
+impl<'a, 'b> Iterator for MatIter<'a, 'b> {
+    type Item = Result<Mat<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let matvar_t = unsafe { ffi::Mat_VarReadNext(self.mat_file.mat_t) };
+        if matvar_t.is_null() {
+            return None;
+        }
+        let name = unsafe { CStr::from_ptr((*matvar_t).name) }.to_str();
+        Some(match name {
+            Ok(name) => Mat::from_ptr(name, matvar_t),
+            Err(e) => {
+                unsafe { ffi::Mat_VarFree(matvar_t) };
+                Err(e.into())
+            }
+        })
+    }
+}
+impl<'a> MatFileRead<'a> {
+    /// Returns an iterator over all the Matlab [Mat] variables of a [MatFileRead]er
+    ///
+    /// The [MatFileRead]er is rewound to the first variable
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::save(data_path)?.var("a", 1f64)?.var("b", vec![1u8, 2, 3])?;
+    /// let mat_file = MatFile::load(data_path)?;
+    /// for mat in mat_file.iter() {
+    ///     let mat = mat?;
+    ///     println!("{}: {:?}", mat.name(), mat.dims());
+    /// }
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn iter(&self) -> MatIter<'a, '_> {
+        self.rewind();
+        MatIter { mat_file: self }
+    }
+    /// Rewinds a [MatFileRead]er to the first variable
+    pub fn rewind(&self) {
+        unsafe { ffi::Mat_Rewind(self.mat_t) };
+    }
+}
 impl<'a> MatFileWrite<'a> {
     /// Write to a [MatFileWrite]r the Matlab [Mat] variable `name`
     ///
@@ -98,7 +176,27 @@ impl<'a> MatFileWrite<'a> {
         Mat<'a>: MayBeFrom<T>,
     {
         let mat: Mat<'a> = MayBeFrom::<T>::maybe_from(name, data)?;
-        self.write(mat);
+        self.write_var(&mat, self.compression)?;
+        Ok(self)
+    }
+    /// Write to a [MatFileWrite]r the Matlab [Mat] variable `name` with zlib [Compression]
+    ///
+    /// The variable is compressed whatever the default [Compression] of the [MatFileWrite]r
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// MatFile::save(data_path)?
+    ///     .var("a", 1f64)?
+    ///     .var_compressed("b", vec![0f64; 1024])?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn var_compressed<S: Into<String>, T>(&self, name: S, data: T) -> Result<&Self>
+    where
+        Mat<'a>: MayBeFrom<T>,
+    {
+        let mat: Mat<'a> = MayBeFrom::<T>::maybe_from(name, data)?;
+        self.write_var(&mat, Compression::Zlib)?;
         Ok(self)
     }
     /// Write to a [MatFileWrite]r the Matlab [Mat] variable `name` as a N-dimensition array [MatArray]
@@ -113,54 +211,131 @@ impl<'a> MatFileWrite<'a> {
         Ok(self)
     }
 }
+impl<'a> MatFileReadWrite<'a> {
+    /// Read from a [MatFileReadWrite]r the Matlab [Mat] variable `name`
+    pub fn var<S: Into<String>, T>(&self, name: S) -> Result<T>
+    where
+        Mat<'a>: MayBeInto<T>,
+    {
+        self.read(name).and_then(|mat| mat.maybe_into())
+    }
+    /// Append to a [MatFileReadWrite]r the Matlab [Mat] variable `name`
+    pub fn append<S: Into<String>, T>(&self, name: S, data: T) -> Result<&Self>
+    where
+        Mat<'a>: MayBeFrom<T>,
+    {
+        let mat: Mat<'a> = MayBeFrom::<T>::maybe_from(name, data)?;
+        self.write_var(&mat, self.compression)?;
+        Ok(self)
+    }
+    /// Delete from a [MatFileReadWrite]r the Matlab variable `name`
+    ///
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::save(data_path)?.var("a", 1f64)?.var("b", 2f64)?;
+    /// MatFile::open_rw(data_path)?.delete("a")?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn delete<S: Into<String>>(&self, name: S) -> Result<&Self> {
+        let c_name = CString::new(name.into())?;
+        let var_name = c_name.to_string_lossy().into_owned();
+        if !self.has_var(&c_name) {
+            return Err(MatioError::MatVarNotFound(var_name));
+        }
+        if unsafe { ffi::Mat_VarDelete(self.mat_t, c_name.as_ptr()) } != 0 {
+            return Err(MatioError::MatVarDelete(var_name));
+        }
+        Ok(self)
+    }
+    /// Rename within a [MatFileReadWrite]r the Matlab variable `old` into `new`
+    ///
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::save(data_path)?.var("a", 1f64)?;
+    /// MatFile::open_rw(data_path)?.rename("a", "b")?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn rename<S: Into<String>, U: Into<String>>(&self, old: S, new: U) -> Result<&Self> {
+        let c_old = CString::new(old.into())?;
+        let c_new = CString::new(new.into())?;
+        if !self.has_var(&c_old) {
+            return Err(MatioError::MatVarNotFound(
+                c_old.to_string_lossy().into_owned(),
+            ));
+        }
+        if self.has_var(&c_new) {
+            return Err(MatioError::MatVarExists(
+                c_new.to_string_lossy().into_owned(),
+            ));
+        }
+        let mut mat = self.read(c_old.to_str()?)?;
+        // matio owns the variable name: it is swapped with the name of a placeholder variable
+        let mut dims = [0usize, 0];
+        let placeholder = unsafe {
+            ffi::Mat_VarCreate(
+                c_new.as_ptr(),
+                ffi::matio_classes_MAT_C_DOUBLE,
+                ffi::matio_types_MAT_T_DOUBLE,
+                2,
+                dims.as_mut_ptr(),
+                ptr::null_mut(),
+                0,
+            )
+        };
+        if placeholder.is_null() {
+            return Err(MatioError::MatVarCreate(
+                c_new.to_string_lossy().into_owned(),
+            ));
+        }
+        unsafe {
+            ptr::swap(&mut (*mat.matvar_t).name, &mut (*placeholder).name);
+            ffi::Mat_VarFree(placeholder);
+        }
+        mat.name = c_new.to_str()?.to_string();
+        let compression = Compression::from_ffi(unsafe { (*mat.matvar_t).compression });
+        self.write_var(&mat, compression)?;
+        self.delete(c_old.to_str()?)
+    }
+}
 impl<'a> Mat<'a> {
+    /// Returns the name of the Matlab variable
+    pub fn name(&self) -> &str {
+        &self.name
+    }
     /// Returns the rank (# of dimensions) of the Matlab variable
     pub fn rank(&self) -> usize {
         unsafe { (*self.matvar_t).rank as usize }
     }
     /// Returns the dimensions of the Matlab variable
     pub fn dims(&self) -> Vec<usize> {
-        let rank = self.rank();
-        let mut dims: Vec<usize> = Vec::with_capacity(rank);
-        unsafe {
-            ptr::copy((*self.matvar_t).dims, dims.as_mut_ptr(), rank);
-            dims.set_len(rank);
-        };
-        dims
+        matvar_dims(unsafe { &*self.matvar_t })
     }
     /// Returns the number of elements of the Matlab variable
     pub fn len(&self) -> usize {
         self.dims().into_iter().product::<usize>() as usize
     }
+    /// Returns true if the Matlab variable is an empty array
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
     pub(crate) fn mat_type(&self) -> Option<MatType> {
         MatType::from_ptr(self.matvar_t)
     }
-    pub(crate) fn as_ptr<S: Into<String>>(name: S, ptr: *mut ffi::matvar_t) -> Result<Self> {
-        Self::from_ptr(name, ptr).map(|mut mat| {
-            mat.as_ref = true;
-            mat
-        })
-    }
     pub(crate) fn from_ptr<S: Into<String>>(name: S, ptr: *mut ffi::matvar_t) -> Result<Self> {
         if let Some(MatType::STRUCT) = MatType::from_ptr(ptr) {
-            let rank = unsafe { (*ptr).rank as usize };
-            let mut dims: Vec<usize> = Vec::with_capacity(rank);
-            unsafe {
-                ptr::copy((*ptr).dims, dims.as_mut_ptr(), rank);
-                dims.set_len(rank);
-            };
-            let nel: usize = dims.iter().product();
+            let nel: usize = matvar_dims(unsafe { &*ptr }).iter().product();
             let n = unsafe { ffi::Mat_VarGetNumberOfFields(ptr) } as usize;
             // fields name
-            let field_names = unsafe {
-                from_raw_parts(ffi::Mat_VarGetStructFieldnames(ptr), n)
-                    .into_iter()
-                    .map(|&s| CStr::from_ptr(s).to_str())
-                    .collect::<std::result::Result<Vec<&str>, std::str::Utf8Error>>()?
-            };
+            let field_names = unsafe { slice(ffi::Mat_VarGetStructFieldnames(ptr), n) }
+                .iter()
+                .map(|&s| unsafe { CStr::from_ptr(s) }.to_str())
+                .collect::<std::result::Result<Vec<&str>, std::str::Utf8Error>>()?;
             // fields data pointer
-            let field_ptr =
-                unsafe { from_raw_parts((*ptr).data as *mut *mut ffi::matvar_t, n * nel) };
+            let field_ptr = unsafe { slice((*ptr).data as *const *mut ffi::matvar_t, n * nel) };
             let mut fields: Vec<Mat> = Vec::new();
             for (name, &ptr) in field_names.into_iter().cycle().zip(field_ptr.iter()) {
                 let mat = Mat::from_ptr(name, ptr)?;
@@ -171,7 +346,35 @@ impl<'a> Mat<'a> {
                 matvar_t: ptr,
                 fields: Some(fields),
                 marker: PhantomData,
-                as_ref: false,
+            })
+        } else if let Some(MatType::CELL) = MatType::from_ptr(ptr) {
+            let nel: usize = matvar_dims(unsafe { &*ptr }).iter().product();
+            let mut cells: Vec<Mat> = Vec::with_capacity(nel);
+            for index in 0..nel {
+                let mut cell_ptr = unsafe { ffi::Mat_VarGetCell(ptr, index as i32) };
+                if cell_ptr.is_null() {
+                    // an unset cell is an empty Matlab array
+                    let mut dims = [0usize, 0];
+                    cell_ptr = unsafe {
+                        ffi::Mat_VarCreate(
+                            ptr::null(),
+                            ffi::matio_classes_MAT_C_DOUBLE,
+                            ffi::matio_types_MAT_T_DOUBLE,
+                            2,
+                            dims.as_mut_ptr(),
+                            ptr::null_mut(),
+                            0,
+                        )
+                    };
+                    unsafe { ffi::Mat_VarSetCell(ptr, index as i32, cell_ptr) };
+                }
+                cells.push(Mat::from_ptr(String::new(), cell_ptr)?);
+            }
+            Ok(Mat {
+                name: name.into(),
+                matvar_t: ptr,
+                fields: Some(cells),
+                marker: PhantomData,
             })
         } else {
             Ok(Mat {
@@ -179,10 +382,100 @@ impl<'a> Mat<'a> {
                 matvar_t: ptr,
                 fields: None,
                 marker: PhantomData,
-                as_ref: false,
             })
         }
     }
+    /// Creates the Matlab cell array `name` of dimensions `dims` from Matlab [Mat] variables
+    ///
+    /// The cells are given in column-major order and can be any Matlab variable,
+    /// including structures and cell arrays, and `dims` has at least 2 dimensions
+    /// ```
+    /// use matio_rs::{Mat, MatFile, MayBeFrom, MayBeInto};
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// let cells = vec![
+    ///     Mat::maybe_from("", 1f64)?,
+    ///     Mat::maybe_from("", "qwerty")?,
+    ///     Mat::maybe_from("", vec![1i32, 2, 3])?,
+    ///     Mat::cell("", vec![Mat::maybe_from("", 2f32)?], vec![1, 1])?,
+    /// ];
+    /// let cell = Mat::cell("c", cells, vec![2, 2])?;
+    /// MatFile::save(data_path)?.write(cell)?;
+    /// let cell = MatFile::load(data_path)?.read("c")?;
+    /// let s: String = cell.get_cell(&[1, 0])?.maybe_into()?;
+    /// assert_eq!(s, "qwerty");
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn cell<S: Into<String>>(name: S, cells: Vec<Mat<'a>>, dims: Vec<usize>) -> Result<Self> {
+        let n: usize = dims.iter().product();
+        if dims.len() < 2 || n != cells.len() {
+            return Err(MatioError::CellSize(cells.len(), dims));
+        }
+        let c_name = CString::new(name.into())?;
+        let matvar_t = unsafe {
+            ffi::Mat_VarCreate(
+                c_name.as_ptr(),
+                ffi::matio_classes_MAT_C_CELL,
+                ffi::matio_types_MAT_T_CELL,
+                dims.len() as i32,
+                dims.as_ptr() as *mut _,
+                ptr::null_mut(),
+                0,
+            )
+        };
+        if matvar_t.is_null() {
+            return Err(MatioError::MatVarCreate(
+                c_name.to_str().unwrap().to_string(),
+            ));
+        }
+        for (index, cell) in cells.iter().enumerate() {
+            unsafe { ffi::Mat_VarSetCell(matvar_t, index as i32, cell.matvar_t) };
+            if unsafe { ffi::Mat_VarGetCell(matvar_t, index as i32) } != cell.matvar_t {
+                // the cells already set are owned by `cells` and are detached before being freed
+                for index in 0..index {
+                    unsafe { ffi::Mat_VarSetCell(matvar_t, index as i32, ptr::null_mut()) };
+                }
+                unsafe { ffi::Mat_VarFree(matvar_t) };
+                return Err(MatioError::MatVarCreate(
+                    c_name.to_str().unwrap().to_string(),
+                ));
+            }
+        }
+        Ok(Mat {
+            name: c_name.to_str().unwrap().to_string(),
+            matvar_t,
+            fields: Some(cells),
+            marker: PhantomData,
+        })
+    }
+    /// Returns the elements of a Matlab cell array in column-major order
+    pub fn cells(&self) -> Result<&[Mat<'a>]> {
+        match (self.mat_type(), self.fields.as_ref()) {
+            (Some(MatType::CELL), Some(cells)) => Ok(cells.as_slice()),
+            _ => Err(MatioError::TypeMismatch(
+                self.name.clone(),
+                MatType::CELL.to_string(),
+                self.mat_type().map(|t| t.to_string()).unwrap_or_default(),
+            )),
+        }
+    }
+    /// Returns the element of a Matlab cell array at the N-dimension `index`
+    pub fn get_cell(&self, index: &[usize]) -> Result<&Mat<'a>> {
+        let cells = self.cells()?;
+        let dims = self.dims();
+        if index.len() != dims.len() || index.iter().zip(&dims).any(|(i, n)| i >= n) {
+            return Err(MatioError::Subset(
+                self.name.clone(),
+                format!("cell index {index:?} is out of bounds of dimensions {dims:?}"),
+            ));
+        }
+        let linear_index = index
+            .iter()
+            .zip(&dims)
+            .rev()
+            .fold(0, |linear_index, (i, n)| linear_index * n + i);
+        Ok(&cells[linear_index])
+    }
     /// Returns the field `name` from a Matlab structure
     pub fn field<S: Into<String>>(&self, name: S) -> Result<Vec<&Mat<'_>>> {
         let fields = if let Some(MatType::STRUCT) = self.mat_type() {
@@ -206,3 +499,26 @@ impl<'a> Mat<'a> {
         }
     }
 }
+
+/// Returns the dimensions of a Matlab variable
+pub(crate) fn matvar_dims(matvar: &ffi::matvar_t) -> Vec<usize> {
+    // SAFETY: matio allocates `rank` dimensions for any variable it creates or reads
+    unsafe { slice(matvar.dims, matvar.rank as usize) }.to_vec()
+}
+
+/// Returns the `n` elements at `ptr`
+///
+/// The slice is empty if `ptr` is null, as the data of an empty Matlab array may be
+///
+/// # Safety
+///
+/// If `ptr` is not null and `n` is not zero, `ptr` must point to `n` initialized
+/// and properly aligned values of type `T` that stay valid, and are not mutated,
+/// for the lifetime `'b` of the returned slice
+pub(crate) unsafe fn slice<'b, T>(ptr: *const T, n: usize) -> &'b [T] {
+    if ptr.is_null() || n == 0 {
+        &[]
+    } else {
+        unsafe { from_raw_parts(ptr, n) }
+    }
+}
@@ -1,11 +1,139 @@
-use crate::{MatioError, Result};
-use std::{fs, io, marker::PhantomData, ops::Deref, path::Path, ptr};
+use crate::{Builder, MatioError, Result, VarInfo};
+use std::{
+    collections::hash_map::RandomState,
+    env,
+    ffi::{CStr, CString},
+    fs::{self, File},
+    hash::BuildHasher,
+    io::{self, Read, Write},
+    marker::PhantomData,
+    ops::Deref,
+    path::{Path, PathBuf},
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Number of attempts at creating a temporary file with a name that is already used
+const TEMP_ATTEMPTS: usize = 16;
 
 /// Mat file
+///
+/// A [MatFile] can be moved to another thread but it cannot be shared between threads:
+/// reading or writing a variable moves the file cursor of the underlying matio handle.
 pub struct MatFile<'a> {
     pub(crate) mat_t: *mut ffi::mat_t,
+    pub(crate) compression: Compression,
+    pub(crate) duplicate: Duplicate,
+    pub(crate) on_close: Option<OnClose>,
     marker: PhantomData<&'a ffi::mat_t>,
 }
+// SAFETY: `mat_t` is owned by the [MatFile] and matio keeps all the state of an open file,
+// (the `FILE` stream) within `mat_t`, without thread-local storage,
+// so the file can be used from any thread as long as it is used from one thread at a time
+unsafe impl<'a> Send for MatFile<'a> {}
+/// File system operation performed once a [MatFile] is closed
+pub(crate) enum OnClose {
+    /// Moves the temporary file `temp` to `path` if the [MatFile] is closed without error,
+    /// otherwise removes it
+    Persist { temp: PathBuf, path: PathBuf },
+    /// Removes the temporary file
+    Remove(PathBuf),
+}
+impl OnClose {
+    fn run(self, closed: bool) -> Result<()> {
+        match self {
+            OnClose::Persist { temp, path } if closed => persist(&temp, &path).map_err(|e| {
+                let _ = fs::remove_file(&temp);
+                MatioError::Persist(path.display().to_string(), e)
+            }),
+            OnClose::Persist { temp, .. } | OnClose::Remove(temp) => {
+                let _ = fs::remove_file(temp);
+                Ok(())
+            }
+        }
+    }
+}
+/// Flushes the temporary file `temp` to disk and moves it to `path`
+///
+/// If `path` exists, its permissions are given to `temp` before the move.
+/// On Unix, the directory of `path` is also flushed to disk for the move to survive a crash
+fn persist(temp: &Path, path: &Path) -> io::Result<()> {
+    let file = fs::OpenOptions::new().write(true).open(temp)?;
+    match fs::metadata(path) {
+        Ok(metadata) => file.set_permissions(metadata.permissions())?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+        Err(e) => return Err(e),
+    }
+    file.sync_all()?;
+    fs::rename(temp, path)?;
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+/// Compression of the variables written to a [MatFile]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// No compression
+    #[default]
+    None,
+    /// zlib compression
+    Zlib,
+}
+impl Compression {
+    pub(crate) fn from_ffi(compression: ffi::matio_compression) -> Self {
+        match compression {
+            ffi::matio_compression_MAT_COMPRESSION_ZLIB => Compression::Zlib,
+            _ => Compression::None,
+        }
+    }
+    pub(crate) fn to_ffi(self) -> ffi::matio_compression {
+        match self {
+            Compression::None => ffi::matio_compression_MAT_COMPRESSION_NONE,
+            Compression::Zlib => ffi::matio_compression_MAT_COMPRESSION_ZLIB,
+        }
+    }
+}
+/// Behavior when writing a variable with the same name as a variable already in a [MatFile]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicate {
+    /// Returns a [MatVarExists](crate::MatioError::MatVarExists) error
+    #[default]
+    Error,
+    /// Deletes the variable already in the [MatFile] before writing the new one
+    Replace,
+}
+/// [MatFile] format version
+///
+/// Version 7.3 (HDF5) mat files are not supported: matio is built without HDF5
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    /// Version 4 mat file
+    MAT4,
+    /// Version 5 mat file
+    #[default]
+    MAT5,
+}
+impl Version {
+    pub(crate) fn from_ffi(version: ffi::mat_ft) -> Option<Self> {
+        match version {
+            ffi::mat_ft_MAT_FT_MAT4 => Some(Version::MAT4),
+            ffi::mat_ft_MAT_FT_MAT5 => Some(Version::MAT5),
+            _ => None,
+        }
+    }
+    pub(crate) fn to_ffi(self) -> ffi::mat_ft {
+        match self {
+            Version::MAT4 => ffi::mat_ft_MAT_FT_MAT4,
+            Version::MAT5 => ffi::mat_ft_MAT_FT_MAT5,
+        }
+    }
+}
 /// [Mat file](crate::MatFile) reader
 pub struct MatFileRead<'a>(MatFile<'a>);
 impl<'a> Deref for MatFileRead<'a> {
@@ -15,41 +143,236 @@ impl<'a> Deref for MatFileRead<'a> {
     }
 }
 impl<'a> MatFileRead<'a> {
-    /// Returns the list of variables within a [MatFile]
-    pub fn info(&self) {
-        let matvar_t = unsafe { ffi::Mat_VarReadNextInfo(self.mat_t) };
-        if !matvar_t.is_null() {
-            let name = unsafe { std::ffi::CStr::from_ptr((*matvar_t).name) };
-            let rank = unsafe { (*matvar_t).rank } as usize;
-            let mut dims: Vec<u64> = Vec::with_capacity(rank);
-            unsafe {
-                ptr::copy((*matvar_t).dims as *mut _, dims.as_mut_ptr(), rank);
-                dims.set_len(rank);
+    /// Closes the [Mat file](crate::MatFile) reader
+    pub fn close(self) -> Result<()> {
+        self.0.close()
+    }
+    /// Returns the metadata of all the variables within a [MatFile]
+    ///
+    /// The [MatFileRead]er is rewound to the first variable before and after the variables are listed,
+    /// so an iteration in progress with [iter](MatFileRead::iter) starts again from the first variable
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::save(data_path)?.var("a", 1f64)?.var("b", vec![1u8, 2, 3])?;
+    /// for info in MatFile::load(data_path)?.info() {
+    ///     println!("{}: {:?} {:?}", info.name, info.class, info.dims);
+    /// }
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn info(&self) -> Vec<VarInfo> {
+        let mut info = vec![];
+        unsafe { ffi::Mat_Rewind(self.mat_t) };
+        loop {
+            let matvar_t = unsafe { ffi::Mat_VarReadNextInfo(self.mat_t) };
+            if matvar_t.is_null() {
+                break;
             }
-            println!(
-                "Matlab var.: {:?} with dims: {:?}",
-                name.to_str().unwrap(),
-                dims
-            );
-            self.info();
+            info.push(VarInfo::from_ptr(matvar_t));
+            unsafe { ffi::Mat_VarFree(matvar_t) };
         }
+        unsafe { ffi::Mat_Rewind(self.mat_t) };
+        info
     }
 }
 /// [Mat file](crate::MatFile) writer
-pub struct MatFileWrite<'a>(MatFile<'a>);
+pub struct MatFileWrite<'a>(pub(crate) MatFile<'a>);
 impl<'a> Deref for MatFileWrite<'a> {
     type Target = MatFile<'a>;
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
+impl<'a> MatFileWrite<'a> {
+    /// Closes the [Mat file](crate::MatFile) writer
+    pub fn close(self) -> Result<()> {
+        self.0.close()
+    }
+    /// Closes the [MatFileWrite]r and returns the content of the mat file
+    ///
+    /// ```
+    /// use matio_rs::MatFile;
+    /// let mat_file = MatFile::save_bytes()?;
+    /// mat_file.var("a", 1f64)?;
+    /// let bytes: Vec<u8> = mat_file.into_bytes()?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn into_bytes(self) -> Result<Vec<u8>> {
+        self.close_with(|path| fs::read(path))
+    }
+    /// Closes the [MatFileWrite]r and streams the content of the mat file into `writer`
+    ///
+    /// ```
+    /// use matio_rs::MatFile;
+    /// let mat_file = MatFile::save_bytes()?;
+    /// mat_file.var("a", 1f64)?;
+    /// mat_file.write_to(std::io::sink())?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn write_to<W: Write>(self, mut writer: W) -> Result<()> {
+        self.close_with(|path| {
+            io::copy(&mut File::open(path)?, &mut writer)?;
+            writer.flush()
+        })
+    }
+    /// Closes the [MatFileWrite]r and calls `f` with the path of the closed file
+    fn close_with<T, F>(self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Path) -> io::Result<T>,
+    {
+        let path = self.filename().unwrap_or_default();
+        let mut mat_file = self.0;
+        let on_close = mat_file.on_close.take();
+        let value = mat_file
+            .close()
+            .and_then(|_| f(&path).map_err(MatioError::from));
+        match on_close {
+            Some(on_close) => on_close.run(value.is_ok()).and(value),
+            None => value,
+        }
+    }
+    /// Sets the default [Compression] of the variables written to a [MatFileWrite]r
+    ///
+    /// ```
+    /// use matio_rs::{Compression, MatFile};
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// MatFile::save(data_path)?
+    ///     .compression(Compression::Zlib)
+    ///     .var("a", vec![0f64; 1024])?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.0.compression = compression;
+        self
+    }
+    /// Sets the [Duplicate] behavior of a [MatFileWrite]r
+    ///
+    /// ```
+    /// use matio_rs::{Duplicate, MatFile};
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// MatFile::save(data_path)?
+    ///     .on_duplicate(Duplicate::Replace)
+    ///     .var("a", 1f64)?
+    ///     .var("a", 2f64)?;
+    /// let a: f64 = MatFile::load(data_path)?.var("a")?;
+    /// assert_eq!(a, 2f64);
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn on_duplicate(mut self, duplicate: Duplicate) -> Self {
+        self.0.duplicate = duplicate;
+        self
+    }
+}
+/// [Mat file](crate::MatFile) reader and writer
+pub struct MatFileReadWrite<'a>(MatFile<'a>);
+impl<'a> Deref for MatFileReadWrite<'a> {
+    type Target = MatFile<'a>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<'a> MatFileReadWrite<'a> {
+    /// Closes the [Mat file](crate::MatFile) reader and writer
+    pub fn close(self) -> Result<()> {
+        self.0.close()
+    }
+    /// Sets the default [Compression] of the variables appended to a [MatFileReadWrite]r
+    ///
+    /// ```
+    /// use matio_rs::{Compression, MatFile};
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::save(data_path)?.var("a", 1f64)?;
+    /// MatFile::open_rw(data_path)?
+    ///     .compression(Compression::Zlib)
+    ///     .append("b", vec![0f64; 1024])?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.0.compression = compression;
+        self
+    }
+    /// Sets the [Duplicate] behavior of a [MatFileReadWrite]r
+    pub fn on_duplicate(mut self, duplicate: Duplicate) -> Self {
+        self.0.duplicate = duplicate;
+        self
+    }
+}
 impl<'a> MatFile<'a> {
     pub(crate) fn from_ptr(mat_t: *mut ffi::mat_t) -> MatFile<'a> {
         MatFile {
             mat_t,
+            compression: Compression::default(),
+            duplicate: Duplicate::default(),
+            on_close: None,
             marker: PhantomData,
         }
     }
+    /// Returns the descriptive header text of the [MatFile]
+    ///
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::builder(data_path).header("simulation results").save()?;
+    /// let header = MatFile::load(data_path)?.header();
+    /// assert_eq!(header.as_deref(), Some("simulation results"));
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn header(&self) -> Option<String> {
+        let header = unsafe { ffi::Mat_GetHeader(self.mat_t) };
+        if header.is_null() {
+            None
+        } else {
+            let header = unsafe { CStr::from_ptr(header) }.to_string_lossy();
+            Some(header.trim_end().to_string())
+        }
+    }
+    /// Returns the format [Version] of the [MatFile]
+    pub fn version(&self) -> Option<Version> {
+        Version::from_ffi(unsafe { ffi::Mat_GetVersion(self.mat_t) })
+    }
+    /// Returns the name of the [MatFile]
+    pub fn filename(&self) -> Option<PathBuf> {
+        let filename = unsafe { ffi::Mat_GetFilename(self.mat_t) };
+        if filename.is_null() {
+            None
+        } else {
+            Some(cstr_to_path(unsafe { CStr::from_ptr(filename) }))
+        }
+    }
+    /// Closes the [MatFile]
+    ///
+    /// A [MatFile] is also closed when it is dropped but any error is then ignored
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// let mat_file = MatFile::save(data_path)?;
+    /// mat_file.var("a", 1f64)?;
+    /// mat_file.close()?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn close(mut self) -> Result<()> {
+        let filename = self
+            .filename()
+            .map(|filename| filename.display().to_string())
+            .unwrap_or_default();
+        let status = unsafe { ffi::Mat_Close(self.mat_t) };
+        self.mat_t = ptr::null_mut();
+        let on_close = self
+            .on_close
+            .take()
+            .map_or(Ok(()), |on_close| on_close.run(status == 0));
+        if status != 0 {
+            Err(MatioError::MatClose(filename))
+        } else {
+            on_close
+        }
+    }
     /// Loads [Mat](crate::Mat) variables from a mat file
     ///
     /// ```
@@ -60,23 +383,81 @@ impl<'a> MatFile<'a> {
     /// # Ok::<(), matio_rs::MatioError>(())
     /// ```
     pub fn load<P: AsRef<Path>>(path: P) -> Result<MatFileRead<'a>> {
-        let attrs = fs::metadata(&path)?;
+        Self::open(path, ffi::mat_acc_MAT_ACC_RDONLY).map(MatFileRead)
+    }
+    /// Loads [Mat](crate::Mat) variables from the content of a mat file
+    ///
+    /// The content is written to a temporary file that is removed when the [MatFileRead]er is closed
+    /// ```
+    /// use matio_rs::MatFile;
+    /// let mat_file = MatFile::save_bytes()?;
+    /// mat_file.var("a", 1f64)?;
+    /// let bytes = mat_file.into_bytes()?;
+    /// let a: f64 = MatFile::load_bytes(&bytes)?.var("a")?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn load_bytes(bytes: &[u8]) -> Result<MatFileRead<'a>> {
+        Self::load_reader(bytes)
+    }
+    /// Loads [Mat](crate::Mat) variables from a mat file streamed from `reader`
+    ///
+    /// The stream is copied to a temporary file that is removed when the [MatFileRead]er is closed
+    /// ```no_run
+    /// use matio_rs::MatFile;
+    /// let a: f64 = MatFile::load_reader(std::io::stdin())?.var("a")?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn load_reader<R: Read>(mut reader: R) -> Result<MatFileRead<'a>> {
+        let (temp, mut file) = temp_file(&env::temp_dir(), "matio-rs", true)?;
+        let mat_file = io::copy(&mut reader, &mut file)
+            .map_err(MatioError::from)
+            .and_then(|_| {
+                drop(file);
+                Self::open(&temp, ffi::mat_acc_MAT_ACC_RDONLY)
+            });
+        match mat_file {
+            Ok(mut mat_file) => {
+                mat_file.on_close = Some(OnClose::Remove(temp));
+                Ok(MatFileRead(mat_file))
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&temp);
+                Err(e)
+            }
+        }
+    }
+    /// Opens an existing mat file for both reading and writing
+    ///
+    /// New variables are appended to the file and the existing ones are left untouched
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::save(data_path)?.var("a", 1f64)?;
+    /// let mat_file = MatFile::open_rw(data_path)?;
+    /// let a: f64 = mat_file.var("a")?;
+    /// mat_file.append("b", 2f64 * a)?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn open_rw<P: AsRef<Path>>(path: P) -> Result<MatFileReadWrite<'a>> {
+        Self::open(path, ffi::mat_acc_MAT_ACC_RDWR).map(MatFileReadWrite)
+    }
+    fn open<P: AsRef<Path>>(path: P, mode: ffi::mat_acc) -> Result<MatFile<'a>> {
+        let attrs = fs::metadata(&path)
+            .map_err(|e| MatioError::NoFile(path.as_ref().display().to_string(), e))?;
         if attrs.is_file() {
-            let mat_name = std::ffi::CString::new(path.as_ref().to_str().unwrap())?;
-            let mat_t =
-                unsafe { ffi::Mat_Open(mat_name.as_ptr(), ffi::mat_acc_MAT_ACC_RDONLY as i32) };
+            let mat_name = path_to_cstring(path.as_ref())?;
+            let mat_t = unsafe { ffi::Mat_Open(mat_name.as_ptr(), mode as i32) };
             if mat_t.is_null() {
-                Err(MatioError::MatOpen(
-                    path.as_ref().to_str().unwrap().to_string(),
-                ))
+                Err(MatioError::MatOpen(path.as_ref().display().to_string()))
             } else {
-                Ok(MatFileRead(MatFile::from_ptr(mat_t)))
+                Ok(MatFile::from_ptr(mat_t))
             }
         } else {
-            Err(MatioError::NoFile(io::Error::new(
-                io::ErrorKind::NotFound,
-                format!("mat file {:?} not found", path.as_ref()),
-            )))
+            Err(MatioError::NoFile(
+                path.as_ref().display().to_string(),
+                io::Error::new(io::ErrorKind::NotFound, "not a file"),
+            ))
         }
     }
     /// Saves [Mat](crate::Mat) variables to a mat file
@@ -89,23 +470,132 @@ impl<'a> MatFile<'a> {
     /// # Ok::<(), matio_rs::MatioError>(())
     /// ```
     pub fn save<P: AsRef<Path>>(path: P) -> Result<MatFileWrite<'a>> {
-        let mat_name = std::ffi::CString::new(path.as_ref().to_str().unwrap())?;
-        let mat_t =
-            unsafe { ffi::Mat_CreateVer(mat_name.as_ptr(), ptr::null(), ffi::mat_ft_MAT_FT_MAT5) };
+        Builder::new(path).save()
+    }
+    /// Saves [Mat](crate::Mat) variables to an in-memory mat file
+    ///
+    /// The variables are written to a temporary file,
+    /// its content is returned with [MatFileWrite::into_bytes] and it is removed
+    /// when the [MatFileWrite]r is closed
+    pub fn save_bytes() -> Result<MatFileWrite<'a>> {
+        let (temp, _) = temp_file(&env::temp_dir(), "matio-rs", true)?;
+        match Self::save(&temp) {
+            Ok(mut mat_file) => {
+                mat_file.0.on_close = Some(OnClose::Remove(temp));
+                Ok(mat_file)
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&temp);
+                Err(e)
+            }
+        }
+    }
+    /// Returns a [Builder] to configure the mat file saved to `path`
+    ///
+    /// ```
+    /// use matio_rs::{MatFile, Version};
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// MatFile::builder(data_path)
+    ///     .version(Version::MAT4)
+    ///     .save()?
+    ///     .var("a", vec![1f64, 2., 3.])?;
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn builder<P: AsRef<Path>>(path: P) -> Builder {
+        Builder::new(path)
+    }
+    pub(crate) fn create<P: AsRef<Path>>(
+        path: P,
+        header: Option<&str>,
+        version: Version,
+    ) -> Result<MatFile<'a>> {
+        let mat_name = path_to_cstring(path.as_ref())?;
+        let header = header.map(CString::new).transpose()?;
+        let mat_t = unsafe {
+            ffi::Mat_CreateVer(
+                mat_name.as_ptr(),
+                header
+                    .as_ref()
+                    .map_or(ptr::null(), |header| header.as_ptr()),
+                version.to_ffi(),
+            )
+        };
         if mat_t.is_null() {
-            Err(MatioError::MatOpen(
-                path.as_ref().to_str().unwrap().to_string(),
-            ))
+            Err(MatioError::MatOpen(path.as_ref().display().to_string()))
         } else {
-            Ok(MatFileWrite(MatFile::from_ptr(mat_t)))
+            Ok(MatFile::from_ptr(mat_t))
+        }
+    }
+}
+
+/// Converts a path into the C string of a mat file name
+///
+/// The path bytes are passed as they are on Unix, elsewhere the path must be valid UTF-8
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Ok(CString::new(path.as_os_str().as_bytes())?)
+    }
+    #[cfg(not(unix))]
+    {
+        let path = path
+            .to_str()
+            .ok_or_else(|| MatioError::PathEncoding(path.display().to_string()))?;
+        Ok(CString::new(path)?)
+    }
+}
+/// Creates a temporary file with a unique and unpredictable name in the directory `dir`
+///
+/// The file is created only if it does not exist yet, so an existing file or symbolic link
+/// is never opened, and, on Unix, a `private` file is readable and writable only by its owner
+pub(crate) fn temp_file(dir: &Path, name: &str, private: bool) -> io::Result<(PathBuf, File)> {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let state = RandomState::new();
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    let mut attempts = 0;
+    loop {
+        let key = state.hash_one(COUNT.fetch_add(1, Ordering::Relaxed));
+        let path = dir.join(format!(".{name}.{key:016x}.tmp"));
+        match options.open(&path) {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < TEMP_ATTEMPTS => {
+                attempts += 1;
+            }
+            file => return file.map(|file| (path, file)),
         }
     }
 }
+/// Converts the C string of a mat file name into a path
+fn cstr_to_path(filename: &CStr) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        PathBuf::from(std::ffi::OsStr::from_bytes(filename.to_bytes()))
+    }
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(filename.to_string_lossy().into_owned())
+    }
+}
 
 impl<'a> Drop for MatFile<'a> {
     fn drop(&mut self) {
-        if unsafe { ffi::Mat_Close(self.mat_t) } != 0 {
-            panic!("failed to close matfile")
+        // best effort: use `close` to catch the errors
+        if !self.mat_t.is_null() {
+            unsafe { ffi::Mat_Close(self.mat_t) };
+        }
+        // a temporary file is never persisted on drop
+        if let Some(on_close) = self.on_close.take() {
+            let _ = on_close.run(false);
         }
     }
 }
@@ -0,0 +1,443 @@
+use crate::{DataType, Mat, MatType, MatioError, MayBeFrom, MayBeInto, Result, mat::slice};
+use std::{
+    ffi::{CStr, CString, c_void},
+    ptr,
+};
+
+/// Matlab sparse matrix in compressed sparse column (CSC) format
+///
+/// The non-zero elements of the column `j` are `data[jc[j]..jc[j + 1]]`
+/// and their row indices are `ir[jc[j]..jc[j + 1]]`.
+/// The elements of a sparse matrix are either `f64`, `bool` or `Complex<f64>` with the `num-complex` feature.
+/// A [SparseMat] is converted from and into a [nalgebra-sparse](https://docs.rs/nalgebra-sparse) `CscMatrix`
+/// with the `nalgebra-sparse` feature and a [faer](https://docs.rs/faer) `SparseColMat` with the `faer` feature.
+/// ```
+/// use matio_rs::{MatFile, SparseMat};
+/// # let file = tempfile::NamedTempFile::new().unwrap();
+/// # let data_path = file.path();
+/// // [1 0 0; 0 0 2]
+/// let sparse = SparseMat::new([2, 3], vec![0, 1], vec![0, 1, 1, 2], vec![1f64, 2f64])?;
+/// MatFile::save(data_path)?.var("s", &sparse)?;
+/// let s: SparseMat<f64> = MatFile::load(data_path)?.var("s")?;
+/// assert_eq!(s, sparse);
+/// # Ok::<(), matio_rs::MatioError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMat<T> {
+    dims: [usize; 2],
+    ir: Vec<usize>,
+    jc: Vec<usize>,
+    data: Vec<T>,
+}
+impl<T> SparseMat<T> {
+    /// Creates a new `dims[0]`x`dims[1]` sparse matrix from the row indices `ir`,
+    /// the column offsets `jc` and the non-zero elements `data`
+    ///
+    /// `jc` has `dims[1]+1` elements, starts at 0 and ends at the number of non-zero elements,
+    /// and the row indices are sorted and unique within each column
+    pub fn new(dims: [usize; 2], ir: Vec<usize>, jc: Vec<usize>, data: Vec<T>) -> Result<Self> {
+        let [nrows, ncols] = dims;
+        let nnz = data.len();
+        if ir.len() != nnz {
+            return Err(MatioError::Sparse(format!(
+                "expected {nnz} row indices, found {}",
+                ir.len()
+            )));
+        }
+        if jc.len() != ncols + 1 {
+            return Err(MatioError::Sparse(format!(
+                "expected {} column offsets, found {}",
+                ncols + 1,
+                jc.len()
+            )));
+        }
+        if jc[0] != 0 || jc[ncols] != nnz || jc.windows(2).any(|w| w[0] > w[1]) {
+            return Err(MatioError::Sparse(format!(
+                "column offsets must increase from 0 to {nnz}"
+            )));
+        }
+        for (j, w) in jc.windows(2).enumerate() {
+            let rows = &ir[w[0]..w[1]];
+            if rows.windows(2).any(|r| r[0] >= r[1]) || rows.last().is_some_and(|&i| i >= nrows) {
+                return Err(MatioError::Sparse(format!(
+                    "row indices of column #{j} must be sorted, unique and less than {nrows}"
+                )));
+            }
+        }
+        Ok(Self { dims, ir, jc, data })
+    }
+    /// Returns the dimensions of the sparse matrix
+    pub fn dims(&self) -> [usize; 2] {
+        self.dims
+    }
+    /// Returns the number of rows
+    pub fn nrows(&self) -> usize {
+        self.dims[0]
+    }
+    /// Returns the number of columns
+    pub fn ncols(&self) -> usize {
+        self.dims[1]
+    }
+    /// Returns the number of non-zero elements
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+    /// Returns the row indices of the non-zero elements
+    pub fn ir(&self) -> &[usize] {
+        &self.ir
+    }
+    /// Returns the column offsets into the non-zero elements
+    pub fn jc(&self) -> &[usize] {
+        &self.jc
+    }
+    /// Returns the non-zero elements
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+    /// Returns the dimensions, the row indices, the column offsets and the non-zero elements
+    pub fn into_parts(self) -> ([usize; 2], Vec<usize>, Vec<usize>, Vec<T>) {
+        (self.dims, self.ir, self.jc, self.data)
+    }
+}
+
+/// Rust types of the elements of a Matlab sparse matrix
+pub trait SparseElement: DataType + Clone {
+    /// Matlab type of a sparse matrix of this element type
+    fn sparse_type() -> MatType;
+    /// Creates the Matlab sparse variable from the `sparse` indices and the non-zero elements `data`
+    fn create(
+        c_name: &CStr,
+        dims: [usize; 2],
+        sparse: ffi::mat_sparse_t,
+        data: &[Self],
+    ) -> *mut ffi::matvar_t;
+    /// Reads `nnz` non-zero elements from the data of a Matlab sparse variable
+    fn read(matvar: &ffi::matvar_t, data: *const c_void, nnz: usize) -> Vec<Self>;
+}
+
+fn create_sparse(
+    c_name: &CStr,
+    dims: [usize; 2],
+    data_type: ffi::matio_types,
+    data: *mut c_void,
+    flags: ffi::matio_flags,
+    mut sparse: ffi::mat_sparse_t,
+) -> *mut ffi::matvar_t {
+    sparse.data = data;
+    let mut dims = dims;
+    unsafe {
+        ffi::Mat_VarCreate(
+            c_name.as_ptr(),
+            ffi::matio_classes_MAT_C_SPARSE,
+            data_type,
+            2,
+            dims.as_mut_ptr(),
+            &mut sparse as *mut _ as *mut c_void,
+            flags as i32,
+        )
+    }
+}
+
+impl SparseElement for f64 {
+    fn sparse_type() -> MatType {
+        MatType::SPARSE
+    }
+    fn create(
+        c_name: &CStr,
+        dims: [usize; 2],
+        sparse: ffi::mat_sparse_t,
+        data: &[Self],
+    ) -> *mut ffi::matvar_t {
+        create_sparse(
+            c_name,
+            dims,
+            ffi::matio_types_MAT_T_DOUBLE,
+            data.as_ptr() as *mut c_void,
+            0,
+            sparse,
+        )
+    }
+    fn read(_matvar: &ffi::matvar_t, data: *const c_void, nnz: usize) -> Vec<Self> {
+        unsafe { slice(data as *const f64, nnz) }.to_vec()
+    }
+}
+
+impl SparseElement for bool {
+    fn sparse_type() -> MatType {
+        MatType::LOGICAL_SPARSE
+    }
+    fn create(
+        c_name: &CStr,
+        dims: [usize; 2],
+        sparse: ffi::mat_sparse_t,
+        data: &[Self],
+    ) -> *mut ffi::matvar_t {
+        let mut data: Vec<f64> = data.iter().map(|&b| b as u8 as f64).collect();
+        create_sparse(
+            c_name,
+            dims,
+            ffi::matio_types_MAT_T_DOUBLE,
+            data.as_mut_ptr() as *mut c_void,
+            ffi::matio_flags_MAT_F_LOGICAL,
+            sparse,
+        )
+    }
+    fn read(matvar: &ffi::matvar_t, data: *const c_void, nnz: usize) -> Vec<Self> {
+        if matvar.data_type == ffi::matio_types_MAT_T_UINT8 {
+            unsafe { slice(data as *const u8, nnz) }
+                .iter()
+                .map(|&b| b != 0)
+                .collect()
+        } else {
+            unsafe { slice(data as *const f64, nnz) }
+                .iter()
+                .map(|&b| b != 0f64)
+                .collect()
+        }
+    }
+}
+
+#[cfg(feature = "num-complex")]
+impl SparseElement for num_complex::Complex<f64> {
+    fn sparse_type() -> MatType {
+        MatType::COMPLEX_SPARSE
+    }
+    fn create(
+        c_name: &CStr,
+        dims: [usize; 2],
+        sparse: ffi::mat_sparse_t,
+        data: &[Self],
+    ) -> *mut ffi::matvar_t {
+        let (mut re, mut im): (Vec<f64>, Vec<f64>) = data.iter().map(|c| (c.re, c.im)).unzip();
+        let mut split = ffi::mat_complex_split_t {
+            Re: re.as_mut_ptr() as *mut c_void,
+            Im: im.as_mut_ptr() as *mut c_void,
+        };
+        create_sparse(
+            c_name,
+            dims,
+            ffi::matio_types_MAT_T_DOUBLE,
+            &mut split as *mut _ as *mut c_void,
+            ffi::matio_flags_MAT_F_COMPLEX,
+            sparse,
+        )
+    }
+    fn read(_matvar: &ffi::matvar_t, data: *const c_void, nnz: usize) -> Vec<Self> {
+        if data.is_null() {
+            return Vec::new();
+        }
+        let split = unsafe { &*(data as *const ffi::mat_complex_split_t) };
+        let (re, im) = unsafe {
+            (
+                slice(split.Re as *const f64, nnz),
+                slice(split.Im as *const f64, nnz),
+            )
+        };
+        re.iter()
+            .zip(im)
+            .map(|(&re, &im)| num_complex::Complex::new(re, im))
+            .collect()
+    }
+}
+
+impl<T: SparseElement> DataType for SparseMat<T> {
+    fn mat_type() -> MatType {
+        T::sparse_type()
+    }
+    fn to_string() -> String {
+        format!("SparseMat<{}>", <T as DataType>::to_string())
+    }
+}
+
+impl<'a, T: SparseElement> MayBeFrom<&SparseMat<T>> for Mat<'a> {
+    fn maybe_from<S: Into<String>>(name: S, data: &SparseMat<T>) -> Result<Self> {
+        let c_name = CString::new(name.into())?;
+        let nnz = data.nnz();
+        // matio stores the row indices and the column offsets as 32-bit integers
+        let index = |i: usize| {
+            ffi::mat_uint32_t::try_from(i).map_err(|_| {
+                MatioError::Sparse(format!("index {i} does not fit in a 32-bit integer"))
+            })
+        };
+        let mut ir = data
+            .ir
+            .iter()
+            .map(|&i| index(i))
+            .collect::<Result<Vec<_>>>()?;
+        let mut jc = data
+            .jc
+            .iter()
+            .map(|&j| index(j))
+            .collect::<Result<Vec<_>>>()?;
+        let sparse = ffi::mat_sparse_t {
+            nzmax: nnz as _,
+            ir: ir.as_mut_ptr(),
+            nir: nnz as _,
+            jc: jc.as_mut_ptr(),
+            njc: jc.len() as _,
+            ndata: nnz as _,
+            data: ptr::null_mut(),
+        };
+        let matvar_t = T::create(&c_name, data.dims, sparse, &data.data);
+        if matvar_t.is_null() {
+            Err(MatioError::MatVarCreate(
+                c_name.to_str().unwrap().to_string(),
+            ))
+        } else {
+            Mat::from_ptr(c_name.to_str().unwrap(), matvar_t)
+        }
+    }
+}
+
+impl<'a, T: SparseElement> MayBeFrom<SparseMat<T>> for Mat<'a> {
+    fn maybe_from<S: Into<String>>(name: S, data: SparseMat<T>) -> Result<Self> {
+        <Mat<'a> as MayBeFrom<&SparseMat<T>>>::maybe_from(name, &data)
+    }
+}
+
+impl<'a, T: SparseElement> MayBeInto<SparseMat<T>> for &Mat<'a> {
+    fn maybe_into(self) -> Result<SparseMat<T>> {
+        match self.mat_type() {
+            Some(mat) if <SparseMat<T> as DataType>::mat_type() == mat => {
+                if self.rank() > 2 {
+                    return Err(MatioError::Rank(self.rank()));
+                }
+                let dims = self.dims();
+                let matvar = unsafe { &*self.matvar_t };
+                let sparse = matvar.data as *const ffi::mat_sparse_t;
+                if sparse.is_null() {
+                    return SparseMat::new(
+                        [dims[0], dims[1]],
+                        vec![],
+                        vec![0; dims[1] + 1],
+                        vec![],
+                    );
+                }
+                let sparse = unsafe { &*sparse };
+                let jc: Vec<usize> = unsafe { slice(sparse.jc, sparse.njc) }
+                    .iter()
+                    .map(|&j| j as usize)
+                    .collect();
+                let nnz = jc.last().copied().unwrap_or_default();
+                let ir: Vec<usize> = unsafe { slice(sparse.ir, nnz.min(sparse.nir)) }
+                    .iter()
+                    .map(|&i| i as usize)
+                    .collect();
+                let data = T::read(matvar, sparse.data, nnz.min(sparse.ndata));
+                SparseMat::new([dims[0], dims[1]], ir, jc, data)
+            }
+            _ => Err(MatioError::TypeMismatch(
+                self.name.clone(),
+                <SparseMat<T> as DataType>::to_string(),
+                self.mat_type().map(|t| t.to_string()).unwrap_or_default(),
+            )),
+        }
+    }
+}
+
+impl<'a, T: SparseElement> MayBeInto<SparseMat<T>> for Mat<'a> {
+    fn maybe_into(self) -> Result<SparseMat<T>> {
+        <&Mat<'a> as MayBeInto<SparseMat<T>>>::maybe_into(&self)
+    }
+}
+
+#[cfg(feature = "nalgebra-sparse")]
+mod nalgebra_sparse_interop {
+    use super::*;
+    use nalgebra_sparse::CscMatrix;
+
+    impl<T: Clone> From<&CscMatrix<T>> for SparseMat<T> {
+        fn from(matrix: &CscMatrix<T>) -> Self {
+            // the row indices of a CscMatrix are already sorted and unique within each column
+            Self {
+                dims: [matrix.nrows(), matrix.ncols()],
+                ir: matrix.row_indices().to_vec(),
+                jc: matrix.col_offsets().to_vec(),
+                data: matrix.values().to_vec(),
+            }
+        }
+    }
+    impl<T> TryFrom<SparseMat<T>> for CscMatrix<T> {
+        type Error = MatioError;
+        fn try_from(sparse: SparseMat<T>) -> Result<Self> {
+            let [nrows, ncols] = sparse.dims;
+            CscMatrix::try_from_csc_data(nrows, ncols, sparse.jc, sparse.ir, sparse.data)
+                .map_err(|e| MatioError::Sparse(e.to_string()))
+        }
+    }
+    impl<'a, T: SparseElement> MayBeFrom<&CscMatrix<T>> for Mat<'a> {
+        fn maybe_from<S: Into<String>>(name: S, matrix: &CscMatrix<T>) -> Result<Self> {
+            <Mat<'a> as MayBeFrom<SparseMat<T>>>::maybe_from(name, SparseMat::from(matrix))
+        }
+    }
+    impl<'a, T: SparseElement> MayBeInto<CscMatrix<T>> for &Mat<'a> {
+        fn maybe_into(self) -> Result<CscMatrix<T>> {
+            <Self as MayBeInto<SparseMat<T>>>::maybe_into(self).and_then(CscMatrix::try_from)
+        }
+    }
+    impl<'a, T: SparseElement> MayBeInto<CscMatrix<T>> for Mat<'a> {
+        fn maybe_into(self) -> Result<CscMatrix<T>> {
+            <&Mat<'a> as MayBeInto<CscMatrix<T>>>::maybe_into(&self)
+        }
+    }
+}
+
+#[cfg(feature = "faer")]
+mod faer_sparse_interop {
+    use super::*;
+    use faer::sparse::{SparseColMat, SymbolicSparseColMat};
+
+    /// The row indices of a faer sparse matrix may be unsorted,
+    /// they are sorted within each column and checked with [SparseMat::new]
+    impl<T: Clone> TryFrom<&SparseColMat<usize, T>> for SparseMat<T> {
+        type Error = MatioError;
+        fn try_from(matrix: &SparseColMat<usize, T>) -> Result<Self> {
+            let ncols = matrix.ncols();
+            let mut ir = Vec::new();
+            let mut jc = Vec::with_capacity(ncols + 1);
+            let mut data = Vec::new();
+            jc.push(0);
+            for j in 0..ncols {
+                let mut column: Vec<_> = matrix
+                    .row_idx_of_col(j)
+                    .zip(matrix.val_of_col(j).iter().cloned())
+                    .collect();
+                column.sort_by_key(|(i, _)| *i);
+                for (i, value) in column {
+                    ir.push(i);
+                    data.push(value);
+                }
+                jc.push(ir.len());
+            }
+            SparseMat::new([matrix.nrows(), ncols], ir, jc, data)
+        }
+    }
+    impl<T> From<SparseMat<T>> for SparseColMat<usize, T> {
+        fn from(sparse: SparseMat<T>) -> Self {
+            let [nrows, ncols] = sparse.dims;
+            // SAFETY: a SparseMat is built either with SparseMat::new, that checks that the
+            // column offsets increase from 0 to the number of non-zero elements and that
+            // the row indices are sorted, unique and less than the number of rows,
+            // or from a CscMatrix that upholds the same invariants
+            let symbolic = unsafe {
+                SymbolicSparseColMat::new_unchecked(nrows, ncols, sparse.jc, None, sparse.ir)
+            };
+            SparseColMat::new(symbolic, sparse.data)
+        }
+    }
+    impl<'a, T: SparseElement> MayBeFrom<&SparseColMat<usize, T>> for Mat<'a> {
+        fn maybe_from<S: Into<String>>(name: S, matrix: &SparseColMat<usize, T>) -> Result<Self> {
+            <Mat<'a> as MayBeFrom<SparseMat<T>>>::maybe_from(name, SparseMat::try_from(matrix)?)
+        }
+    }
+    impl<'a, T: SparseElement> MayBeInto<SparseColMat<usize, T>> for &Mat<'a> {
+        fn maybe_into(self) -> Result<SparseColMat<usize, T>> {
+            <Self as MayBeInto<SparseMat<T>>>::maybe_into(self).map(SparseColMat::from)
+        }
+    }
+    impl<'a, T: SparseElement> MayBeInto<SparseColMat<usize, T>> for Mat<'a> {
+        fn maybe_into(self) -> Result<SparseColMat<usize, T>> {
+            <&Mat<'a> as MayBeInto<SparseColMat<usize, T>>>::maybe_into(&self)
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{DataType, MatClass, MatFile, MatioError, Numeric, Result, mat::matvar_dims};
+use std::{ffi::CString, marker::PhantomData};
+
+/// Header of a numeric Matlab variable, without the variable data
+pub(crate) struct Header {
+    name: String,
+    matvar_t: *mut ffi::matvar_t,
+}
+impl Header {
+    /// Reads the header of the Matlab variable `name` and checks that it matches the Rust type `T`
+    pub(crate) fn read<T: Numeric>(mat_file: &MatFile<'_>, name: String) -> Result<Self> {
+        let c_name = CString::new(name)?;
+        let matvar_t = unsafe { ffi::Mat_VarReadInfo(mat_file.mat_t, c_name.as_ptr()) };
+        let name = c_name.to_string_lossy().into_owned();
+        if matvar_t.is_null() {
+            return Err(MatioError::MatVarNotFound(name));
+        }
+        let header = Self { name, matvar_t };
+        let (class, is_complex, is_logical) = unsafe {
+            (
+                MatClass::from_ffi((*matvar_t).class_type),
+                (*matvar_t).isComplex != 0,
+                (*matvar_t).isLogical != 0,
+            )
+        };
+        if class != T::mat_class() || is_complex || is_logical {
+            let found = match (is_complex, is_logical) {
+                (true, _) => format!("COMPLEX {class:?}"),
+                (_, true) => "LOGICAL".to_string(),
+                _ => format!("{class:?}"),
+            };
+            return Err(MatioError::TypeMismatch(
+                header.name.clone(),
+                <T as DataType>::to_string(),
+                found,
+            ));
+        }
+        Ok(header)
+    }
+    pub(crate) fn dims(&self) -> Vec<usize> {
+        matvar_dims(unsafe { &*self.matvar_t })
+    }
+    pub(crate) fn len(&self) -> usize {
+        self.dims().into_iter().product()
+    }
+    fn subset_error<S: Into<String>>(&self, msg: S) -> MatioError {
+        MatioError::Subset(self.name.clone(), msg.into())
+    }
+    fn to_int(&self, value: usize) -> Result<i32> {
+        i32::try_from(value).map_err(|_| self.subset_error(format!("{value} is too large")))
+    }
+    /// Returns the index of the last of `edge` elements separated by `stride` elements from `start`
+    fn last_index(&self, start: usize, stride: usize, edge: usize) -> Result<usize> {
+        edge.checked_sub(1)
+            .and_then(|edge| edge.checked_mul(stride))
+            .and_then(|offset| offset.checked_add(start))
+            .ok_or_else(|| {
+                self.subset_error(format!(
+                    "start {start}, stride {stride} and edge {edge} are out of range"
+                ))
+            })
+    }
+    /// Reads the hyperslab of the variable defined by `start`, `stride` and `edge`
+    pub(crate) fn read_data<T: Numeric>(
+        &self,
+        mat_file: &MatFile<'_>,
+        start: &[usize],
+        stride: &[usize],
+        edge: &[usize],
+    ) -> Result<Vec<T>> {
+        let dims = self.dims();
+        let rank = dims.len();
+        if start.len() != rank || stride.len() != rank || edge.len() != rank {
+            return Err(
+                self.subset_error(format!("expected start, stride and edge of length {rank}"))
+            );
+        }
+        for (d, &n) in dims.iter().enumerate() {
+            if stride[d] == 0 || edge[d] == 0 {
+                return Err(self.subset_error("stride and edge must be strictly positive"));
+            }
+            if self.last_index(start[d], stride[d], edge[d])? >= n {
+                return Err(
+                    self.subset_error(format!("dimension #{d} of size {n} is out of bounds"))
+                );
+            }
+        }
+        let mut start = start
+            .iter()
+            .map(|&x| self.to_int(x))
+            .collect::<Result<Vec<i32>>>()?;
+        let mut stride = stride
+            .iter()
+            .map(|&x| self.to_int(x))
+            .collect::<Result<Vec<i32>>>()?;
+        let mut edge = edge
+            .iter()
+            .map(|&x| self.to_int(x))
+            .collect::<Result<Vec<i32>>>()?;
+        let n = edge.iter().map(|&x| x as usize).product();
+        let mut data = vec![T::default(); n];
+        let status = unsafe {
+            ffi::Mat_VarReadData(
+                mat_file.mat_t,
+                self.matvar_t,
+                data.as_mut_ptr() as *mut std::ffi::c_void,
+                start.as_mut_ptr(),
+                stride.as_mut_ptr(),
+                edge.as_mut_ptr(),
+            )
+        };
+        if status != 0 {
+            Err(MatioError::MatVarRead(self.name.clone()))
+        } else {
+            Ok(data)
+        }
+    }
+    /// Reads `edge` elements of the variable separated by `stride` elements from the linear index `start`
+    pub(crate) fn read_linear_data<T: Numeric>(
+        &self,
+        mat_file: &MatFile<'_>,
+        start: usize,
+        stride: usize,
+        edge: usize,
+    ) -> Result<Vec<T>> {
+        let n = self.len();
+        if stride == 0 || edge == 0 {
+            return Err(self.subset_error("stride and edge must be strictly positive"));
+        }
+        if self.last_index(start, stride, edge)? >= n {
+            return Err(self.subset_error(format!("variable of length {n} is out of bounds")));
+        }
+        let mut data = vec![T::default(); edge];
+        let status = unsafe {
+            ffi::Mat_VarReadDataLinear(
+                mat_file.mat_t,
+                self.matvar_t,
+                data.as_mut_ptr() as *mut std::ffi::c_void,
+                self.to_int(start)?,
+                self.to_int(stride)?,
+                self.to_int(edge)?,
+            )
+        };
+        if status != 0 {
+            Err(MatioError::MatVarRead(self.name.clone()))
+        } else {
+            Ok(data)
+        }
+    }
+}
+impl Drop for Header {
+    fn drop(&mut self) {
+        unsafe { ffi::Mat_VarFree(self.matvar_t) };
+    }
+}
+
+impl<'a> MatFile<'a> {
+    /// Reads a hyperslab of the numeric Matlab variable `name`
+    ///
+    /// The hyperslab starts at the index `start` and has `edge` elements
+    /// separated by `stride` elements along each dimension of the variable.
+    /// Only the hyperslab is read from the [MatFile] and it is returned in column-major order.
+    ///
+    /// Reading the frames 2 and 4 of a 3x4x5 cube
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// let cube: Vec<f64> = (0..60).map(|x| x as f64).collect();
+    /// MatFile::save(data_path)?.array("cube", &cube, vec![3, 4, 5])?;
+    /// let frames: Vec<f64> = MatFile::load(data_path)?
+    ///     .read_slab("cube", &[0, 0, 1], &[1, 1, 2], &[3, 4, 2])?;
+    /// assert_eq!(frames[..12], cube[12..24]);
+    /// assert_eq!(frames[12..], cube[36..48]);
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn read_slab<S: Into<String>, T: Numeric>(
+        &self,
+        name: S,
+        start: &[usize],
+        stride: &[usize],
+        edge: &[usize],
+    ) -> Result<Vec<T>> {
+        Header::read::<T>(self, name.into())?.read_data(self, start, stride, edge)
+    }
+}
+
+/// Iterator over the chunks of a numeric Matlab variable
+///
+/// Each chunk is read from the [MatFile] as a `Vec<T>` of consecutive elements,
+/// the last chunk may be shorter than the others
+pub struct Chunks<'a, 'b, T> {
+    mat_file: &'b MatFile<'a>,
+    header: Header,
+    len: usize,
+    size: usize,
+    index: usize,
+    marker: PhantomData<T>,
+}
+impl<'a, 'b, T: Numeric> Iterator for Chunks<'a, 'b, T> {
+    type Item = Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        let edge = self.size.min(self.len - self.index);
+        let chunk = self
+            .header
+            .read_linear_data(self.mat_file, self.index, 1, edge);
+        self.index = if chunk.is_ok() {
+            self.index + edge
+        } else {
+            self.len
+        };
+        Some(chunk)
+    }
+}
+
+impl<'a> MatFile<'a> {
+    /// Reads `edge` elements separated by `stride` elements from the linear index `start`
+    /// of the numeric Matlab variable `name`
+    ///
+    /// Only the requested elements are read from the [MatFile]
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// let data: Vec<i32> = (0..100).collect();
+    /// MatFile::save(data_path)?.var("data", &data)?;
+    /// let odd: Vec<i32> = MatFile::load(data_path)?.read_linear("data", 1, 2, 50)?;
+    /// assert_eq!(odd, (1..100).step_by(2).collect::<Vec<i32>>());
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn read_linear<S: Into<String>, T: Numeric>(
+        &self,
+        name: S,
+        start: usize,
+        stride: usize,
+        edge: usize,
+    ) -> Result<Vec<T>> {
+        Header::read::<T>(self, name.into())?.read_linear_data(self, start, stride, edge)
+    }
+    /// Returns an iterator over the chunks of `size` elements of the numeric Matlab variable `name`
+    ///
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// let data: Vec<f64> = (0..1000).map(|x| x as f64).collect();
+    /// MatFile::save(data_path)?.var("data", &data)?;
+    /// let mat_file = MatFile::load(data_path)?;
+    /// for chunk in mat_file.chunks::<_, f64>("data", 128)? {
+    ///     let chunk = chunk?;
+    ///     println!("{:?}", chunk.iter().sum::<f64>());
+    /// }
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn chunks<S: Into<String>, T: Numeric>(
+        &self,
+        name: S,
+        size: usize,
+    ) -> Result<Chunks<'a, '_, T>> {
+        let header = Header::read::<T>(self, name.into())?;
+        if size == 0 {
+            return Err(header.subset_error("chunk size must be strictly positive"));
+        }
+        Ok(Chunks {
+            mat_file: self,
+            len: header.len(),
+            header,
+            size,
+            index: 0,
+            marker: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{MatClass, MatDataType, MatFile, MatioError, Result, mat::matvar_dims};
+use std::{
+    ffi::{CStr, CString},
+    slice::from_raw_parts,
+};
+
+/// Matlab variable metadata
+///
+/// The metadata is read from the variable header without loading the variable data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarInfo {
+    /// Variable name
+    pub name: String,
+    /// Variable dimensions
+    pub dims: Vec<usize>,
+    /// Matlab class
+    pub class: MatClass,
+    /// Data type of the variable elements
+    pub data_type: MatDataType,
+    /// Complex flag
+    pub is_complex: bool,
+    /// Logical flag
+    pub is_logical: bool,
+    /// Global flag
+    pub is_global: bool,
+    /// Size in bytes of the variable once loaded in memory
+    ///
+    /// The size is computed by matio (`Mat_VarGetSize`) from the variable header,
+    /// it is not the size of the variable stored in the file that may be compressed
+    pub mem_bytes: usize,
+}
+impl VarInfo {
+    pub(crate) fn from_ptr(ptr: *mut ffi::matvar_t) -> Self {
+        let matvar = unsafe { &*ptr };
+        let name = if matvar.name.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(matvar.name) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        Self {
+            name,
+            dims: matvar_dims(matvar),
+            class: MatClass::from_ffi(matvar.class_type),
+            data_type: MatDataType::from_ffi(matvar.data_type),
+            is_complex: matvar.isComplex != 0,
+            is_logical: matvar.isLogical != 0,
+            is_global: matvar.isGlobal != 0,
+            mem_bytes: unsafe { ffi::Mat_VarGetSize(ptr) },
+        }
+    }
+    /// Returns the number of elements of the Matlab variable
+    pub fn len(&self) -> usize {
+        self.dims.iter().product()
+    }
+    /// Returns true if the Matlab variable has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a> MatFile<'a> {
+    /// Checks if the Matlab variable `name` is in the [MatFile]
+    ///
+    /// Only the variable header is read
+    /// ```
+    /// use matio_rs::MatFile;
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::save(data_path)?.var("a", 1f64)?;
+    /// let mat_file = MatFile::load(data_path)?;
+    /// assert!(mat_file.contains("a"));
+    /// assert!(!mat_file.contains("b"));
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn contains<S: Into<String>>(&self, name: S) -> bool {
+        CString::new(name.into()).is_ok_and(|c_name| self.has_var(&c_name))
+    }
+    /// Returns the metadata of the Matlab variable `name` without reading the variable data
+    ///
+    /// ```
+    /// use matio_rs::{MatClass, MatFile};
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let data_path = file.path();
+    /// # MatFile::save(data_path)?.var("a", vec![1f64; 12])?;
+    /// let info = MatFile::load(data_path)?.var_info("a")?;
+    /// assert_eq!(info.class, MatClass::DOUBLE);
+    /// assert_eq!(info.dims, vec![1, 12]);
+    /// # Ok::<(), matio_rs::MatioError>(())
+    /// ```
+    pub fn var_info<S: Into<String>>(&self, name: S) -> Result<VarInfo> {
+        let c_name = CString::new(name.into())?;
+        let matvar_t = unsafe { ffi::Mat_VarReadInfo(self.mat_t, c_name.as_ptr()) };
+        if matvar_t.is_null() {
+            Err(MatioError::MatVarNotFound(
+                c_name.to_string_lossy().into_owned(),
+            ))
+        } else {
+            let info = VarInfo::from_ptr(matvar_t);
+            unsafe { ffi::Mat_VarFree(matvar_t) };
+            Ok(info)
+        }
+    }
+    /// Checks if the Matlab variable `name` is in the directory of the variable names
+    /// that matio keeps for the [MatFile]
+    ///
+    /// The directory is read from the file once and then updated by matio
+    /// with the names of the variables that are written or deleted
+    pub(crate) fn in_dir(&self, c_name: &CStr) -> bool {
+        let mut n = 0;
+        let dir = unsafe { ffi::Mat_GetDir(self.mat_t, &mut n) };
+        if dir.is_null() {
+            return false;
+        }
+        unsafe { from_raw_parts(dir, n) }
+            .iter()
+            .any(|&name| !name.is_null() && unsafe { CStr::from_ptr(name) } == c_name)
+    }
+    pub(crate) fn has_var(&self, c_name: &CStr) -> bool {
+        let matvar_t = unsafe { ffi::Mat_VarReadInfo(self.mat_t, c_name.as_ptr()) };
+        if matvar_t.is_null() {
+            false
+        } else {
+            unsafe { ffi::Mat_VarFree(matvar_t) };
+            true
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Mat, MatFileRead, MatFileWrite, MayBeFrom, Result};
+
+/// In-memory Matlab workspace
+///
+/// A [Workspace] is an ordered map of Matlab [Mat] variables indexed by their names
+/// ```
+/// use matio_rs::{MatFile, Workspace};
+/// # let file = tempfile::NamedTempFile::new().unwrap();
+/// # let data_path = file.path();
+/// # MatFile::save(data_path)?.var("a", 1f64)?.var("b", vec![1u8, 2, 3])?;
+/// let mut workspace = Workspace::load(&MatFile::load(data_path)?)?;
+/// workspace.var("a", 2f64)?;
+/// workspace.remove("b");
+/// workspace.var("c", "qwerty")?;
+/// workspace.save(&MatFile::save(data_path)?)?;
+/// # Ok::<(), matio_rs::MatioError>(())
+/// ```
+#[derive(Default)]
+pub struct Workspace<'a> {
+    vars: Vec<Mat<'a>>,
+}
+impl<'a> Workspace<'a> {
+    /// Creates an empty [Workspace]
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Loads all the variables of a [MatFileRead]er into a [Workspace]
+    pub fn load(mat_file: &MatFileRead<'a>) -> Result<Self> {
+        let mut workspace = Self::new();
+        for mat in mat_file.iter() {
+            workspace.insert(mat?);
+        }
+        Ok(workspace)
+    }
+    /// Writes all the variables of the [Workspace] to a [MatFileWrite]r
+    pub fn save(&self, mat_file: &MatFileWrite<'a>) -> Result<()> {
+        for mat in &self.vars {
+            mat_file.write_var(mat, mat_file.compression)?;
+        }
+        Ok(())
+    }
+    /// Inserts a Matlab [Mat] variable into the [Workspace]
+    ///
+    /// If a variable with the same name is already in the [Workspace],
+    /// it is replaced in place and returned
+    pub fn insert(&mut self, mat: Mat<'a>) -> Option<Mat<'a>> {
+        match self.vars.iter_mut().find(|var| var.name == mat.name) {
+            Some(var) => Some(std::mem::replace(var, mat)),
+            None => {
+                self.vars.push(mat);
+                None
+            }
+        }
+    }
+    /// Inserts the Rust variable `data` into the [Workspace] as the Matlab variable `name`
+    ///
+    /// If a variable with the same name is already in the [Workspace],
+    /// it is replaced in place and returned
+    pub fn var<S: Into<String>, T>(&mut self, name: S, data: T) -> Result<Option<Mat<'a>>>
+    where
+        Mat<'a>: MayBeFrom<T>,
+    {
+        let mat: Mat<'a> = MayBeFrom::<T>::maybe_from(name, data)?;
+        Ok(self.insert(mat))
+    }
+    /// Returns the Matlab [Mat] variable `name`
+    pub fn get(&self, name: &str) -> Option<&Mat<'a>> {
+        self.vars.iter().find(|var| var.name == name)
+    }
+    /// Removes and returns the Matlab [Mat] variable `name`
+    pub fn remove(&mut self, name: &str) -> Option<Mat<'a>> {
+        self.vars
+            .iter()
+            .position(|var| var.name == name)
+            .map(|i| self.vars.remove(i))
+    }
+    /// Checks if the Matlab variable `name` is in the [Workspace]
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+    /// Returns the names of the variables in the [Workspace]
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.vars.iter().map(|var| var.name.as_str())
+    }
+    /// Returns an iterator over the Matlab [Mat] variables of the [Workspace]
+    pub fn iter(&self) -> std::slice::Iter<'_, Mat<'a>> {
+        self.vars.iter()
+    }
+    /// Returns the number of variables in the [Workspace]
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+    /// Returns true if the [Workspace] has no variables
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+}
+impl<'a> IntoIterator for Workspace<'a> {
+    type Item = Mat<'a>;
+    type IntoIter = std::vec::IntoIter<Mat<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vars.into_iter()
+    }
+}
+impl<'a, 'b> IntoIterator for &'b Workspace<'a> {
+    type Item = &'b Mat<'a>;
+    type IntoIter = std::slice::Iter<'b, Mat<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vars.iter()
+    }
+}
@@ -82,6 +82,474 @@ fn test_readwrite() {
     assert_eq!(b, bb);
 }
 
+#[test]
+fn test_open_rw() {
+    let path = root();
+    MatFile::save(&path).unwrap().var("a", 1f64).unwrap();
+    {
+        let mat_file = MatFile::open_rw(&path).unwrap();
+        let a: f64 = mat_file.var("a").unwrap();
+        mat_file.append("b", vec![a; 3]).unwrap();
+    }
+    let mat_file = MatFile::load(&path).unwrap();
+    let a: f64 = mat_file.var("a").unwrap();
+    assert_eq!(a, 1f64);
+    let b: Vec<f64> = mat_file.var("b").unwrap();
+    assert_eq!(b, vec![1f64; 3]);
+}
+
+#[test]
+fn test_delete_rename() {
+    let path = root();
+    polytype(&path);
+    {
+        let mat_file = MatFile::open_rw(&path).unwrap();
+        mat_file.delete("a").unwrap();
+        mat_file.rename("b", "bb").unwrap();
+        assert!(matches!(
+            mat_file.delete("a"),
+            Err(MatioError::MatVarNotFound(_))
+        ));
+        assert!(matches!(
+            mat_file.rename("bb", "c"),
+            Err(MatioError::MatVarExists(_))
+        ));
+    }
+    let mat_file = MatFile::load(&path).unwrap();
+    assert!(mat_file.read("a").is_err());
+    let b: f32 = mat_file.var("bb").unwrap();
+    assert_eq!(b, 2f32);
+    let c: Vec<u16> = mat_file.var("c").unwrap();
+    assert_eq!(c, vec![3u16; 3]);
+}
+
+#[test]
+fn test_compression() {
+    let path = root();
+    let b = (0..1024).map(|x| (x as f64).sin()).collect::<Vec<f64>>();
+    MatFile::save(&path)
+        .unwrap()
+        .compression(Compression::Zlib)
+        .var("a", vec![0f64; 1024])
+        .unwrap();
+    {
+        let mat_file = MatFile::open_rw(&path).unwrap();
+        mat_file.append("b", &b).unwrap();
+    }
+    let mat_file = MatFile::load(&path).unwrap();
+    let info = mat_file.info();
+    assert_eq!(info.len(), 2);
+    let a: Vec<f64> = mat_file.var("a").unwrap();
+    assert_eq!(a, vec![0f64; 1024]);
+    let bb: Vec<f64> = mat_file.var("b").unwrap();
+    assert_eq!(b, bb);
+    let path = root();
+    MatFile::save(&path)
+        .unwrap()
+        .var_compressed("b", &b)
+        .unwrap();
+    let bb: Vec<f64> = MatFile::load(&path).unwrap().var("b").unwrap();
+    assert_eq!(b, bb);
+    let len = std::fs::metadata(&path).unwrap().len();
+    MatFile::open_rw(&path)
+        .unwrap()
+        .compression(Compression::Zlib)
+        .append("c", vec![0f64; 1024])
+        .unwrap();
+    assert!(std::fs::metadata(&path).unwrap().len() - len < 1024);
+    let c: Vec<f64> = MatFile::load(&path).unwrap().var("c").unwrap();
+    assert_eq!(c, vec![0f64; 1024]);
+}
+
+#[test]
+fn test_builder() {
+    let path = root();
+    Builder::new(&path)
+        .version(Version::MAT4)
+        .save()
+        .unwrap()
+        .var("a", vec![1f64, 2., 3.])
+        .unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    assert_eq!(mat_file.version(), Some(Version::MAT4));
+    let a: Vec<f64> = mat_file.var("a").unwrap();
+    assert_eq!(a, vec![1f64, 2., 3.]);
+    assert!(matches!(
+        Builder::new(root()).header("x".repeat(117)).save(),
+        Err(MatioError::Header(117))
+    ));
+}
+
+#[test]
+fn test_save_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.mat");
+    Builder::new(&path)
+        .mode(SaveMode::CreateNew)
+        .save()
+        .unwrap()
+        .var("a", 1f64)
+        .unwrap();
+    assert!(matches!(
+        Builder::new(&path).mode(SaveMode::CreateNew).save(),
+        Err(MatioError::FileExists(_))
+    ));
+    {
+        let mat_file = Builder::new(&path).mode(SaveMode::Atomic).save().unwrap();
+        mat_file.var("a", 2f64).unwrap();
+    }
+    let a: f64 = MatFile::load(&path).unwrap().var("a").unwrap();
+    assert_eq!(a, 1f64);
+    let mat_file = Builder::new(&path).mode(SaveMode::Atomic).save().unwrap();
+    mat_file.var("a", 3f64).unwrap();
+    mat_file.close().unwrap();
+    let a: f64 = MatFile::load(&path).unwrap().var("a").unwrap();
+    assert_eq!(a, 3f64);
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+        let mat_file = Builder::new(&path).mode(SaveMode::Atomic).save().unwrap();
+        mat_file.var("a", 4f64).unwrap();
+        mat_file.close().unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+}
+
+#[test]
+fn test_bytes() {
+    let mat_file = MatFile::save_bytes().unwrap();
+    mat_file.var("a", 1f64).unwrap();
+    mat_file.var("b", vec![1u8, 2, 3]).unwrap();
+    let bytes = mat_file.into_bytes().unwrap();
+    let path = root();
+    std::fs::write(&path, &bytes).unwrap();
+    let a: f64 = MatFile::load(&path).unwrap().var("a").unwrap();
+    assert_eq!(a, 1f64);
+    let mat_file = MatFile::load_bytes(&bytes).unwrap();
+    let temp = mat_file.filename().unwrap();
+    let b: Vec<u8> = mat_file.var("b").unwrap();
+    assert_eq!(b, vec![1u8, 2, 3]);
+    mat_file.close().unwrap();
+    assert!(!temp.exists());
+}
+
+#[test]
+fn test_read_write_adapters() {
+    let mat_file = MatFile::save_bytes().unwrap();
+    mat_file.var("a", 1f64).unwrap();
+    let mut buffer = Vec::new();
+    mat_file.write_to(&mut buffer).unwrap();
+    let mat_file = MatFile::load_reader(std::io::Cursor::new(buffer)).unwrap();
+    let a: f64 = mat_file.var("a").unwrap();
+    assert_eq!(a, 1f64);
+
+    struct Failing;
+    impl std::io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("stream closed"))
+        }
+    }
+    assert!(matches!(
+        MatFile::load_reader(Failing),
+        Err(MatioError::Io(e)) if e.to_string() == "stream closed"
+    ));
+}
+
+#[test]
+fn test_send_sync() {
+    fn is_send<T: Send>() {}
+    is_send::<MatFile>();
+    is_send::<MatFileRead>();
+    is_send::<MatFileWrite>();
+    is_send::<MatFileReadWrite>();
+    is_send::<Mat>();
+    is_send::<Workspace>();
+
+    let path = root();
+    let mat_file = MatFile::save(&path).unwrap();
+    let mat = Mat::maybe_from("a", vec![1f64, 2., 3.]).unwrap();
+    std::thread::spawn(move || {
+        mat_file.write(mat).unwrap();
+    })
+    .join()
+    .unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    let a: Vec<f64> = std::thread::spawn(move || mat_file.var("a").unwrap())
+        .join()
+        .unwrap();
+    assert_eq!(a, vec![1f64, 2., 3.]);
+}
+
+#[test]
+fn test_duplicate() {
+    let path = root();
+    let mat_file = MatFile::save(&path).unwrap();
+    mat_file.var("a", 1f64).unwrap();
+    assert!(matches!(
+        mat_file.var("a", 2f64),
+        Err(MatioError::MatVarExists(name)) if name == "a"
+    ));
+    assert!(matches!(
+        mat_file.write(Mat::maybe_from("a", 2f64).unwrap()),
+        Err(MatioError::MatVarExists(name)) if name == "a"
+    ));
+    mat_file.close().unwrap();
+    let a: f64 = MatFile::load(&path).unwrap().var("a").unwrap();
+    assert_eq!(a, 1f64);
+
+    let mat_file = MatFile::builder(&path)
+        .on_duplicate(Duplicate::Replace)
+        .save()
+        .unwrap();
+    mat_file.var("a", 1f64).unwrap().var("b", 2f64).unwrap();
+    mat_file.var("a", 3f64).unwrap();
+    mat_file.close().unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    assert_eq!(mat_file.info().len(), 2);
+    let a: f64 = mat_file.var("a").unwrap();
+    assert_eq!(a, 3f64);
+}
+
+#[test]
+fn test_logical() {
+    let path = root();
+    let mask = vec![true, false, false, true];
+    MatFile::save(&path)
+        .unwrap()
+        .var("a", true)
+        .unwrap()
+        .var("mask", &mask)
+        .unwrap()
+        .array("cube", &[true; 8], vec![2, 2, 2])
+        .unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    let a: bool = mat_file.var("a").unwrap();
+    assert!(a);
+    let m: Vec<bool> = mat_file.var("mask").unwrap();
+    assert_eq!(m, mask);
+    let info = mat_file.var_info("mask").unwrap();
+    assert!(info.is_logical);
+    assert_eq!(info.class, MatClass::UINT8);
+    let cube: Vec<bool> = mat_file.var("cube").unwrap();
+    assert_eq!(cube, vec![true; 8]);
+    let m: Result<Vec<u8>> = mat_file.var("mask");
+    assert!(matches!(m, Err(MatioError::TypeMismatch(..))));
+    let m: Result<Vec<u8>> = mat_file.read_linear("mask", 0, 1, 4);
+    assert!(matches!(m, Err(MatioError::TypeMismatch(..))));
+    assert!(matches!(
+        mat_file.chunks::<_, u8>("mask", 2),
+        Err(MatioError::TypeMismatch(..))
+    ));
+}
+
+#[test]
+fn test_sparse() {
+    let path = root();
+    // [1 0 3; 0 2 0]
+    let sparse =
+        SparseMat::new([2, 3], vec![0, 1, 0], vec![0, 1, 2, 3], vec![1f64, 2., 3.]).unwrap();
+    let mask = SparseMat::new([2, 2], vec![1], vec![0, 0, 1], vec![true]).unwrap();
+    MatFile::save(&path)
+        .unwrap()
+        .var("s", &sparse)
+        .unwrap()
+        .var("m", &mask)
+        .unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    let s: SparseMat<f64> = mat_file.var("s").unwrap();
+    assert_eq!(s, sparse);
+    assert_eq!(mat_file.var_info("s").unwrap().class, MatClass::SPARSE);
+    let m: SparseMat<bool> = mat_file.var("m").unwrap();
+    assert_eq!(m, mask);
+    let s: Result<Vec<f64>> = mat_file.var("s");
+    assert!(matches!(s, Err(MatioError::TypeMismatch(..))));
+    let m: Result<SparseMat<f64>> = mat_file.var("m");
+    assert!(matches!(m, Err(MatioError::TypeMismatch(..))));
+
+    assert!(matches!(
+        SparseMat::new([2, 2], vec![1, 0], vec![0, 2, 2], vec![1f64, 2.]),
+        Err(MatioError::Sparse(_))
+    ));
+    assert!(matches!(
+        SparseMat::new([2, 2], vec![2], vec![0, 1, 1], vec![1f64]),
+        Err(MatioError::Sparse(_))
+    ));
+    let nrows = u32::MAX as usize + 2;
+    let tall = SparseMat::new([nrows, 1], vec![nrows - 1], vec![0, 1], vec![1f64]).unwrap();
+    assert!(matches!(
+        Mat::maybe_from("tall", tall),
+        Err(MatioError::Sparse(_))
+    ));
+}
+
+#[test]
+fn test_cell() {
+    let path = root();
+    let s = Mat::maybe_from(
+        "",
+        vec![
+            Mat::maybe_from("a", 1f64).unwrap(),
+            Mat::maybe_from("b", vec![1u8, 2]).unwrap(),
+        ],
+    )
+    .unwrap();
+    let nested = Mat::cell("", vec![Mat::maybe_from("", "qwerty").unwrap()], vec![1, 1]).unwrap();
+    let cells = vec![
+        Mat::maybe_from("", 1f64).unwrap(),
+        Mat::maybe_from("", vec![1i32, 2, 3]).unwrap(),
+        s,
+        nested,
+        Mat::maybe_from("", 2f64).unwrap(),
+        Mat::maybe_from("", 3f64).unwrap(),
+    ];
+    let cell = Mat::cell("c", cells, vec![1, 3, 2]).unwrap();
+    let numbers = Mat::cell(
+        "n",
+        (0..4)
+            .map(|i| Mat::maybe_from("", i as f64).unwrap())
+            .collect(),
+        vec![2, 2],
+    )
+    .unwrap();
+    MatFile::save(&path)
+        .unwrap()
+        .write(cell)
+        .unwrap()
+        .write(numbers)
+        .unwrap();
+
+    let mat_file = MatFile::load(&path).unwrap();
+    let cell = mat_file.read("c").unwrap();
+    assert_eq!(cell.dims(), vec![1, 3, 2]);
+    assert_eq!(cell.cells().unwrap().len(), 6);
+    let a: f64 = cell.get_cell(&[0, 0, 0]).unwrap().maybe_into().unwrap();
+    assert_eq!(a, 1f64);
+    let v: Vec<i32> = cell.get_cell(&[0, 1, 0]).unwrap().maybe_into().unwrap();
+    assert_eq!(v, vec![1, 2, 3]);
+    let b: Vec<u8> = cell.get_cell(&[0, 2, 0]).unwrap().field("b").unwrap()[0]
+        .maybe_into()
+        .unwrap();
+    assert_eq!(b, vec![1, 2]);
+    let s: Vec<String> = cell.get_cell(&[0, 0, 1]).unwrap().maybe_into().unwrap();
+    assert_eq!(s, vec!["qwerty".to_string()]);
+    let c: f64 = cell.get_cell(&[0, 2, 1]).unwrap().maybe_into().unwrap();
+    assert_eq!(c, 3f64);
+    assert!(matches!(
+        cell.get_cell(&[1, 0, 0]),
+        Err(MatioError::Subset(..))
+    ));
+
+    let n: Vec<f64> = mat_file.var("n").unwrap();
+    assert_eq!(n, vec![0., 1., 2., 3.]);
+    assert!(matches!(
+        Mat::cell("c", vec![], vec![1, 1]),
+        Err(MatioError::CellSize(0, _))
+    ));
+    assert!(matches!(
+        Mat::cell("c", vec![Mat::maybe_from("", 1f64).unwrap()], vec![1]),
+        Err(MatioError::CellSize(1, _))
+    ));
+}
+
+#[test]
+fn test_char_encoding() {
+    let mat = Mat::maybe_from("s", "héllo wörld").unwrap();
+    assert_eq!(mat.dims(), vec![1, 11]);
+    let mat = Mat::char_matrix("s", &["ü", "abc"], CharEncoding::Utf8).unwrap();
+    assert_eq!(mat.dims(), vec![2, 3]);
+    let path = root();
+    MatFile::save(&path)
+        .unwrap()
+        .write(Mat::char("a", "héllo wörld", CharEncoding::Utf16).unwrap())
+        .unwrap()
+        .write(Mat::char("b", "héllo wörld", CharEncoding::Utf8).unwrap())
+        .unwrap()
+        .write(Mat::char_matrix("c", &["one", "three", "ü"], CharEncoding::Utf16).unwrap())
+        .unwrap()
+        .write(Mat::char("d", "", CharEncoding::Utf16).unwrap())
+        .unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    let a: String = mat_file.var("a").unwrap();
+    assert_eq!(a, "héllo wörld");
+    let b: String = mat_file.var("b").unwrap();
+    assert_eq!(b, "héllo wörld");
+    assert_eq!(mat_file.var_info("b").unwrap().dims, vec![1, 11]);
+    let c: Vec<String> = mat_file.var("c").unwrap();
+    assert_eq!(c, vec!["one  ", "three", "ü    "]);
+    assert_eq!(mat_file.var_info("c").unwrap().dims, vec![3, 5]);
+    let c: Result<String> = mat_file.var("c");
+    assert!(matches!(c, Err(MatioError::CharRows(_, 3))));
+    let d: String = mat_file.var("d").unwrap();
+    assert_eq!(d, "");
+    assert_eq!(mat_file.var_info("d").unwrap().dims, vec![0, 0]);
+}
+
+#[test]
+fn test_empty() {
+    let path = root();
+    MatFile::save(&path)
+        .unwrap()
+        .var("e", Vec::<f64>::new())
+        .unwrap()
+        .var("none", None::<f64>)
+        .unwrap()
+        .var("some", Some(vec![1f64, 2.]))
+        .unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    let e: Vec<f64> = mat_file.var("e").unwrap();
+    assert!(e.is_empty());
+    let mat: Mat = mat_file.var("e").unwrap();
+    assert_eq!(mat.dims(), vec![0, 0]);
+    assert!(mat.is_empty());
+    let e: Result<f64> = mat_file.var("e");
+    assert!(matches!(e, Err(MatioError::Empty(..))));
+    let none: Option<f64> = mat_file.var("none").unwrap();
+    assert_eq!(none, None);
+    let none: Option<Vec<f64>> = mat_file.var("e").unwrap();
+    assert_eq!(none, None);
+    let some: Option<Vec<f64>> = mat_file.var("some").unwrap();
+    assert_eq!(some, Some(vec![1f64, 2.]));
+
+    MatFile::save(&path)
+        .unwrap()
+        .var("s", "")
+        .unwrap()
+        .var("c", Vec::<&str>::new())
+        .unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    assert_eq!(mat_file.var_info("s").unwrap().dims, vec![0, 0]);
+    assert_eq!(mat_file.var_info("c").unwrap().dims, vec![0, 0]);
+    let s: String = mat_file.var("s").unwrap();
+    assert_eq!(s, "");
+}
+
+#[test]
+fn test_file_metadata() {
+    let path = root();
+    Builder::new(&path).header("matio-rs test").save().unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    assert_eq!(mat_file.header().as_deref(), Some("matio-rs test"));
+    assert_eq!(mat_file.version(), Some(Version::MAT5));
+    assert_eq!(mat_file.filename(), Some(path));
+    mat_file.close().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_non_utf8_path() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(OsStr::from_bytes(b"data\xff.mat"));
+    MatFile::save(&path).unwrap().var("a", 1f64).unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    assert_eq!(mat_file.filename(), Some(path));
+    let a: f64 = mat_file.var("a").unwrap();
+    assert_eq!(a, 1f64);
+}
+
 fn polytype(path: &PathBuf) {
     MatFile::save(path)
         .unwrap()
@@ -106,6 +574,138 @@ fn test_polytype() {
     assert_eq!(c, vec![3u16; 3]);
 }
 
+#[test]
+fn test_info() {
+    let path = root();
+    polytype(&path);
+    let info = MatFile::load(&path).unwrap().info();
+    let names: Vec<_> = info.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+    let classes: Vec<_> = info.iter().map(|i| i.class).collect();
+    assert_eq!(
+        classes,
+        vec![MatClass::INT8, MatClass::SINGLE, MatClass::UINT16]
+    );
+    assert_eq!(info[2].dims, vec![1, 3]);
+    assert!(!info[2].is_complex);
+}
+
+#[test]
+fn test_var_info() {
+    let path = root();
+    polytype(&path);
+    let mat_file = MatFile::load(&path).unwrap();
+    assert!(mat_file.contains("c"));
+    assert!(!mat_file.contains("d"));
+    let info = mat_file.var_info("c").unwrap();
+    assert_eq!(info.class, MatClass::UINT16);
+    assert_eq!(info.dims, vec![1, 3]);
+    assert_eq!(info.len(), 3);
+    assert!(matches!(
+        mat_file.var_info("d"),
+        Err(MatioError::MatVarNotFound(_))
+    ));
+    assert!(matches!(
+        mat_file.read("d"),
+        Err(MatioError::MatVarNotFound(_))
+    ));
+}
+
+#[test]
+fn test_iter() {
+    let path = root();
+    polytype(&path);
+    let mat_file = MatFile::load(&path).unwrap();
+    let names: Vec<String> = mat_file
+        .iter()
+        .map(|mat| mat.unwrap().name().to_string())
+        .collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+    let mut iter = mat_file.iter();
+    let a: i8 = iter.next().unwrap().unwrap().maybe_into().unwrap();
+    assert_eq!(a, 1i8);
+    mat_file.rewind();
+    assert_eq!(iter.next().unwrap().unwrap().name(), "a");
+    assert_eq!(iter.count(), 2);
+}
+
+#[test]
+fn test_workspace() {
+    let path = root();
+    polytype(&path);
+    let mut workspace = Workspace::load(&MatFile::load(&path).unwrap()).unwrap();
+    assert_eq!(workspace.len(), 3);
+    workspace.var("a", 10i8).unwrap().unwrap();
+    workspace.remove("b").unwrap();
+    assert!(workspace.var("d", "qwerty").unwrap().is_none());
+    assert_eq!(workspace.names().collect::<Vec<_>>(), vec!["a", "c", "d"]);
+    let path = root();
+    workspace.save(&MatFile::save(&path).unwrap()).unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    let a: i8 = mat_file.var("a").unwrap();
+    assert_eq!(a, 10i8);
+    assert!(mat_file.read("b").is_err());
+    let c: Vec<u16> = mat_file.var("c").unwrap();
+    assert_eq!(c, vec![3u16; 3]);
+    let d: String = mat_file.var("d").unwrap();
+    assert_eq!(d, "qwerty");
+}
+
+#[test]
+fn test_read_slab() {
+    let path = root();
+    let data: Vec<f64> = (0..24).map(|x| x as f64).collect();
+    MatFile::save(&path)
+        .unwrap()
+        .array("a", &data, vec![3, 4, 2])
+        .unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    let slab: Vec<f64> = mat_file
+        .read_slab("a", &[0, 1, 0], &[2, 2, 1], &[2, 2, 2])
+        .unwrap();
+    assert_eq!(slab, vec![3., 5., 9., 11., 15., 17., 21., 23.]);
+    assert!(matches!(
+        mat_file.read_slab::<_, f32>("a", &[0, 0, 0], &[1, 1, 1], &[1, 1, 1]),
+        Err(MatioError::TypeMismatch(..))
+    ));
+    assert!(matches!(
+        mat_file.read_slab::<_, f64>("a", &[0, 0, 1], &[1, 1, 1], &[1, 1, 2]),
+        Err(MatioError::Subset(..))
+    ));
+    assert!(matches!(
+        mat_file.read_slab::<_, f64>("a", &[0, 0, 0], &[1, usize::MAX, 1], &[1, 2, 1]),
+        Err(MatioError::Subset(..))
+    ));
+}
+
+#[test]
+fn test_read_linear() {
+    let path = root();
+    let data: Vec<u32> = (0..100).collect();
+    MatFile::save(&path).unwrap().var("a", &data).unwrap();
+    let mat_file = MatFile::load(&path).unwrap();
+    let a: Vec<u32> = mat_file.read_linear("a", 10, 3, 5).unwrap();
+    assert_eq!(a, vec![10, 13, 16, 19, 22]);
+    let chunks = mat_file
+        .chunks::<_, u32>("a", 30)
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+        vec![30, 30, 30, 10]
+    );
+    assert_eq!(chunks.concat(), data);
+    assert!(matches!(
+        mat_file.read_linear::<_, u32>("a", 90, 2, 6),
+        Err(MatioError::Subset(..))
+    ));
+    assert!(matches!(
+        mat_file.read_linear::<_, u32>("a", usize::MAX, 1, 2),
+        Err(MatioError::Subset(..))
+    ));
+}
+
 fn save_struct(path: &PathBuf) {
     let mat_a = Mat::maybe_from("fa", 123f64).unwrap();
     let v = vec![0i32, 1, 2, 3, 4];
@@ -115,7 +715,7 @@ fn save_struct(path: &PathBuf) {
     let mat_struct = Mat::maybe_from("s", data).unwrap();
 
     let mat_file = MatFile::save(path).unwrap();
-    mat_file.write(mat_struct);
+    mat_file.write(mat_struct).unwrap();
 }
 
 #[test]
@@ -158,7 +758,7 @@ fn save_struct_nested(path: &PathBuf) {
     let mat_struct = Mat::maybe_from("s", data).unwrap();
 
     let mat_file = MatFile::save(path).unwrap();
-    mat_file.write(mat_struct);
+    mat_file.write(mat_struct).unwrap();
 }
 #[test]
 fn test_struct_nested() {
@@ -200,7 +800,7 @@ fn save_struct_array(path: &PathBuf) {
     let mat_struct = Mat::maybe_from("s", data).unwrap();
 
     let mat_file = MatFile::save(path).unwrap();
-    mat_file.write(mat_struct);
+    mat_file.write(mat_struct).unwrap();
 }
 
 #[test]
@@ -245,6 +845,27 @@ mod nalgebra_matio {
     }
 }
 
+#[cfg(feature = "nalgebra-sparse")]
+mod nalgebra_sparse_matio {
+    use super::*;
+    #[test]
+    fn test_nalgebra_sparse() {
+        let coo = nalgebra_sparse::CooMatrix::try_from_triplets(
+            4,
+            3,
+            vec![0, 3, 1, 2],
+            vec![0, 0, 1, 2],
+            vec![1f64, 2., 3., 4.],
+        )
+        .unwrap();
+        let csc = nalgebra_sparse::CscMatrix::from(&coo);
+        let path = root();
+        MatFile::save(&path).unwrap().var("csc", &csc).unwrap();
+        let m: nalgebra_sparse::CscMatrix<f64> = MatFile::load(path).unwrap().var("csc").unwrap();
+        assert_eq!(csc, m);
+    }
+}
+
 #[cfg(feature = "faer")]
 mod faer_matio {
     use super::*;
@@ -277,4 +898,163 @@ mod faer_matio {
             .zip(m.col_iter())
             .all(|(x, y)| x.iter().zip(y.iter()).all(|(x, y)| x == y)));
     }
+
+    #[test]
+    fn test_faer_sparse() {
+        let sparse =
+            SparseMat::new([3, 3], vec![0, 1, 2], vec![0, 1, 2, 3], vec![1f64, 2., 3.]).unwrap();
+        let faer_sparse = faer::sparse::SparseColMat::<usize, f64>::from(sparse.clone());
+        let path = root();
+        MatFile::save(&path)
+            .unwrap()
+            .var("s", &faer_sparse)
+            .unwrap();
+        let s: faer::sparse::SparseColMat<usize, f64> =
+            MatFile::load(&path).unwrap().var("s").unwrap();
+        assert_eq!(SparseMat::try_from(&s).unwrap(), sparse);
+
+        // [2; 0; 1] with unsorted row indices
+        let unsorted = faer::sparse::SparseColMat::new(
+            faer::sparse::SymbolicSparseColMat::new_unsorted_checked(
+                3,
+                1,
+                vec![0, 2],
+                None,
+                vec![2, 0],
+            ),
+            vec![1f64, 2.],
+        );
+        let sparse = SparseMat::try_from(&unsorted).unwrap();
+        assert_eq!(sparse.ir(), &[0, 2]);
+        assert_eq!(sparse.data(), &[2., 1.]);
+    }
+}
+
+#[cfg(feature = "rayon")]
+mod rayon_matio {
+    use super::*;
+    #[test]
+    fn test_par_var() {
+        let mut paths: Vec<_> = (0..8).map(|_| root()).collect();
+        for (i, path) in paths.iter().enumerate() {
+            MatFile::save(path)
+                .unwrap()
+                .var("a", vec![i as f64; 3])
+                .unwrap();
+        }
+        paths.push(root());
+        let a = MatFile::par_var::<_, Vec<f64>>(&paths, "a");
+        assert_eq!(a.len(), 9);
+        for (i, a) in a.iter().take(8).enumerate() {
+            assert_eq!(a.as_ref().unwrap(), &vec![i as f64; 3]);
+        }
+        assert!(a[8].is_err());
+    }
+
+    #[test]
+    fn test_par_vars() {
+        let paths: Vec<_> = (0..4).map(|_| root()).collect();
+        for path in &paths {
+            MatFile::save(path)
+                .unwrap()
+                .var("a", 1f64)
+                .unwrap()
+                .var("b", 2f64)
+                .unwrap();
+        }
+        let vars = MatFile::par_vars::<_, _, f64>(&paths, &["a", "b"]);
+        assert!(
+            vars.iter()
+                .all(|v| v.as_ref().unwrap() == &vec![1f64, 2f64])
+        );
+    }
+}
+
+#[cfg(feature = "num-complex")]
+mod complex_matio {
+    use super::*;
+    use num_complex::Complex;
+
+    #[test]
+    fn test_complex_scalar() {
+        let path = root();
+        let z = Complex::new(1f64, -2f64);
+        MatFile::save(&path).unwrap().var("z", z).unwrap();
+        let mat_file = MatFile::load(&path).unwrap();
+        let zz: Complex<f64> = mat_file.var("z").unwrap();
+        assert_eq!(z, zz);
+        let info = mat_file.var_info("z").unwrap();
+        assert!(info.is_complex);
+        let re: Result<f64> = mat_file.var("z");
+        assert!(matches!(re, Err(MatioError::TypeMismatch(..))));
+    }
+
+    #[test]
+    fn test_complex_vector() {
+        let path = root();
+        let z: Vec<_> = (0..5)
+            .map(|i| Complex::new(i as f32, -(i as f32)))
+            .collect();
+        MatFile::save(&path).unwrap().var("z", &z).unwrap();
+        let zz: Vec<Complex<f32>> = MatFile::load(&path).unwrap().var("z").unwrap();
+        assert_eq!(z, zz);
+    }
+
+    #[test]
+    fn test_complex_array() {
+        let path = root();
+        let z: Vec<_> = (0..24)
+            .map(|i| Complex::new(i as f64, 2. * i as f64))
+            .collect();
+        MatFile::save(&path)
+            .unwrap()
+            .array("z", &z, vec![3, 4, 2])
+            .unwrap();
+        let mat_file = MatFile::load(&path).unwrap();
+        assert_eq!(mat_file.var_info("z").unwrap().dims, vec![3, 4, 2]);
+        let zz: Vec<Complex<f64>> = mat_file.var("z").unwrap();
+        assert_eq!(z, zz);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_complex_nalgebra() {
+        let path = root();
+        let z = nalgebra::DMatrix::from_iterator(3, 2, (0..6).map(|i| Complex::new(i as f64, 1.)));
+        MatFile::save(&path).unwrap().var("z", &z).unwrap();
+        let zz: nalgebra::DMatrix<Complex<f64>> = MatFile::load(&path).unwrap().var("z").unwrap();
+        assert_eq!(z, zz);
+    }
+
+    #[cfg(feature = "faer")]
+    #[test]
+    fn test_complex_faer() {
+        let path = root();
+        let data: Vec<_> = (0..6).map(|i| Complex::new(1f64, i as f64)).collect();
+        let z = faer::mat::MatRef::from_column_major_slice(data.as_slice(), 3, 2).cloned();
+        MatFile::save(&path).unwrap().var("z", &z).unwrap();
+        let zz: faer::mat::Mat<Complex<f64>> = MatFile::load(&path).unwrap().var("z").unwrap();
+        assert_eq!(z, zz);
+    }
+
+    #[test]
+    fn test_complex_sparse() {
+        let path = root();
+        let sparse = SparseMat::new(
+            [3, 2],
+            vec![0, 2, 1],
+            vec![0, 2, 3],
+            vec![
+                Complex::new(1f64, 1.),
+                Complex::new(2., -1.),
+                Complex::new(0., 3.),
+            ],
+        )
+        .unwrap();
+        MatFile::save(&path).unwrap().var("s", &sparse).unwrap();
+        let mat_file = MatFile::load(&path).unwrap();
+        assert!(mat_file.var_info("s").unwrap().is_complex);
+        let s: SparseMat<Complex<f64>> = mat_file.var("s").unwrap();
+        assert_eq!(s, sparse);
+    }
 }